@@ -0,0 +1,47 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use opencv::core::{Mat, Rect, Vec3b};
+use opencv::prelude::*;
+
+fn make_mat() -> Mat {
+	let mut mat = Mat::new_rows_cols_with_default(480, 640, Vec3b::opencv_type(), 0.into()).unwrap();
+	for (i, px) in mat.data_typed_mut::<Vec3b>().unwrap().iter_mut().enumerate() {
+		*px = Vec3b::from([i as u8, (i >> 8) as u8, (i >> 16) as u8]);
+	}
+	mat
+}
+
+fn sum(px: &Vec3b) -> u64 {
+	px.iter().map(|&x| u64::from(x)).sum()
+}
+
+fn mat_iter(c: &mut Criterion) {
+	let mat = make_mat();
+	let roi = Mat::roi(&mat, Rect::new(10, 10, 600, 400)).unwrap();
+	let mut group = c.benchmark_group("mat_iter");
+	group.bench_function("iter", |b| {
+		b.iter(|| black_box(&mat).iter::<Vec3b>().unwrap().map(|(_, px)| sum(&px)).sum::<u64>())
+	});
+	group.bench_function("iter_fast", |b| {
+		b.iter(|| black_box(&mat).iter_fast::<Vec3b>().unwrap().map(sum).sum::<u64>())
+	});
+	group.bench_function("rows_iter", |b| {
+		b.iter(|| {
+			black_box(&mat)
+				.rows_iter::<Vec3b>()
+				.unwrap()
+				.map(|row| row.iter().map(sum).sum::<u64>())
+				.sum::<u64>()
+		})
+	});
+	group.bench_function("iter_roi", |b| {
+		b.iter(|| black_box(&roi).iter::<Vec3b>().unwrap().map(|(_, px)| sum(&px)).sum::<u64>())
+	});
+	group.bench_function("iter_fast_roi", |b| {
+		b.iter(|| black_box(&roi).iter_fast::<Vec3b>().unwrap().map(sum).sum::<u64>())
+	});
+	group.finish();
+}
+
+criterion_group!(benches, mat_iter);
+criterion_main!(benches);
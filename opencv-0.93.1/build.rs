@@ -7,14 +7,19 @@ use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use binding_generator::handle_running_binding_generator;
+use cache::{BindingsCache, CacheEntry};
 use docs::handle_running_in_docsrs;
 use generator::BindingGenerator;
 use library::Library;
 use once_cell::sync::{Lazy, OnceCell};
+use pregenerated::PregeneratedBindings;
+use report::ProbeReport;
 use semver::{Version, VersionReq};
 
 #[path = "build/binding-generator.rs"]
 mod binding_generator;
+#[path = "build/cache.rs"]
+mod cache;
 #[path = "build/cmake_probe.rs"]
 pub mod cmake_probe;
 #[path = "build/docs.rs"]
@@ -23,6 +28,12 @@ mod docs;
 mod generator;
 #[path = "build/library.rs"]
 pub mod library;
+#[path = "build/pregenerated.rs"]
+mod pregenerated;
+#[path = "build/report.rs"]
+mod report;
+#[path = "build/vendored.rs"]
+mod vendored;
 
 type Result<T, E = Box<dyn std::error::Error>> = std::result::Result<T, E>;
 
@@ -48,7 +59,7 @@ static OPENCV_BRANCH_4: Lazy<VersionReq> =
 
 /// Environment vars that affect the build, the source will be rebuilt if those change, the contents of those vars will also
 /// be present in the debug log
-static AFFECTING_ENV_VARS: [&str; 18] = [
+static AFFECTING_ENV_VARS: [&str; 25] = [
 	"OPENCV_PACKAGE_NAME",
 	"OPENCV_PKGCONFIG_NAME",
 	"OPENCV_CMAKE_NAME",
@@ -59,6 +70,13 @@ static AFFECTING_ENV_VARS: [&str; 18] = [
 	"OPENCV_INCLUDE_PATHS",
 	"OPENCV_DISABLE_PROBES",
 	"OPENCV_MSVC_CRT",
+	"OPENCV_PREGENERATED_BINDINGS",
+	"OPENCV_PREGENERATED_BINDINGS_EXPORT_DIR",
+	"OPENCV_BINDINGS_CACHE_DIR",
+	"OPENCV_SOURCE_DIR",
+	"OPENCV_CONTRIB_SOURCE_DIR",
+	"OPENCV_CMAKE_ARGS",
+	"CMAKE_TOOLCHAIN_FILE",
 	"CMAKE_PREFIX_PATH",
 	"OpenCV_DIR",
 	"PKG_CONFIG_PATH",
@@ -251,7 +269,7 @@ fn setup_rerun() -> Result<()> {
 	Ok(())
 }
 
-fn build_wrapper(mut cc: cc::Build) {
+fn build_wrapper(mut cc: cc::Build, cache: Option<&CacheEntry>) -> Result<()> {
 	eprintln!("=== Compiler information: {:#?}", cc.get_compiler());
 	let modules = MODULES.get().expect("MODULES not initialized");
 	static SUPPORTED_MODULES: [&str; 67] = [
@@ -326,17 +344,54 @@ fn build_wrapper(mut cc: cc::Build) {
 	for module in SUPPORTED_MODULES {
 		println!("cargo:rustc-check-cfg=cfg(ocvrs_has_module_{module})"); // replace with cargo:: syntax when MSRV is 1.77
 	}
+	// exposed at runtime in `opencv::BuildInfo::crate_modules`
+	println!("cargo:rustc-env=OCVRS_MODULES={}", modules.join(",")); // replace with cargo:: syntax when MSRV is 1.77
+	let mut sources = vec![];
 	for module in modules.iter() {
 		println!("cargo:rustc-cfg=ocvrs_has_module_{module}"); // replace with cargo:: syntax when MSRV is 1.77
-		cc.file(OUT_DIR.join(format!("{module}.cpp")));
+		sources.push(OUT_DIR.join(format!("{module}.cpp")));
 		let manual_cpp = SRC_CPP_DIR.join(format!("manual-{module}.cpp"));
 		if manual_cpp.exists() {
-			cc.file(manual_cpp);
+			sources.push(manual_cpp);
+		}
+	}
+	cc.files(&sources);
+	let cached_library = cache.map(|cache| cache.library(&cc, &sources, &OUT_DIR)).transpose()?;
+	if let Some(cached_library) = &cached_library {
+		if cached_library.restore(&OUT_DIR)? {
+			return Ok(());
 		}
 	}
 	let start = Instant::now();
 	cc.compile("ocvrs");
 	eprintln!("=== Total cpp build time: {:?}", start.elapsed());
+	if let Some(cached_library) = cached_library {
+		cached_library.store(&OUT_DIR)?;
+	}
+	Ok(())
+}
+
+/// Finds the OpenCV library, its header dirs and modules, records the results in the `report`
+///
+/// Returns the library, the header dir and the module header dir.
+fn probe_opencv(report: &mut ProbeReport) -> Result<(Library, PathBuf, PathBuf)> {
+	let opencv = Library::probe(report)?;
+	report.set_library(&opencv);
+	eprintln!("=== OpenCV library configuration: {opencv:#?}");
+	let opencv_header_dir = opencv
+		.include_paths
+		.iter()
+		.find(|p| get_version_header(p).is_some())
+		.expect("Discovered OpenCV include paths is empty or contains non-existent paths")
+		.clone();
+	let opencv_module_header_dir = get_module_header_dir(&opencv_header_dir).expect("Can't find OpenCV module header dir");
+	eprintln!(
+		"=== Detected OpenCV module header dir at: {}",
+		opencv_module_header_dir.display()
+	);
+	make_modules(&opencv_module_header_dir)?;
+	report.set_modules(MODULES.get().expect("MODULES not initialized"));
+	Ok((opencv, opencv_header_dir, opencv_module_header_dir))
 }
 
 fn main() -> Result<()> {
@@ -373,8 +428,16 @@ fn main() -> Result<()> {
 		eprintln!("===   {feature}");
 	}
 
-	let opencv = Library::probe()?;
-	eprintln!("=== OpenCV library configuration: {opencv:#?}");
+	let mut probe_report = ProbeReport::new();
+	let probed = probe_opencv(&mut probe_report);
+	if let Err(e) = &probed {
+		probe_report.set_error(&e.to_string());
+	}
+	// the report is most useful when the probing fails, so it's written before bailing out
+	if let Err(e) = probe_report.write(&OUT_DIR) {
+		eprintln!("=== Can't write OpenCV probe report: {e}");
+	}
+	let (opencv, opencv_header_dir, opencv_module_header_dir) = probed?;
 	println!("cargo:rustc-check-cfg=cfg(ocvrs_opencv_branch_4)"); // replace with cargo:: syntax when MSRV is 1.77
 	println!("cargo:rustc-check-cfg=cfg(ocvrs_opencv_branch_34)"); // replace with cargo:: syntax when MSRV is 1.77
 	println!("cargo:rustc-check-cfg=cfg(ocvrs_opencv_branch_32)"); // replace with cargo:: syntax when MSRV is 1.77
@@ -390,20 +453,8 @@ fn main() -> Result<()> {
 			opencv.version
 		);
 	}
-	let opencv_header_dir = opencv
-		.include_paths
-		.iter()
-		.find(|p| get_version_header(p).is_some())
-		.expect("Discovered OpenCV include paths is empty or contains non-existent paths");
-
-	let opencv_module_header_dir = get_module_header_dir(opencv_header_dir).expect("Can't find OpenCV module header dir");
-	eprintln!(
-		"=== Detected OpenCV module header dir at: {}",
-		opencv_module_header_dir.display()
-	);
-	make_modules(&opencv_module_header_dir)?;
 
-	if let Some(header_version) = get_version_from_headers(opencv_header_dir) {
+	if let Some(header_version) = get_version_from_headers(&opencv_header_dir) {
 		if header_version != opencv.version {
 			panic!(
 				"OpenCV version from the headers: {header_version} (at {}) must match version of the OpenCV library: {} (include paths: {:?})",
@@ -426,10 +477,28 @@ fn main() -> Result<()> {
 	setup_rerun()?;
 
 	let ffi_export_suffix = format!("_{}", pkg_version.replace(".", "_"));
-	let binding_generator = BindingGenerator::new(build_script_path);
-	binding_generator.generate_wrapper(opencv_header_dir, &opencv, &ffi_export_suffix)?;
+	let modules = MODULES.get().expect("MODULES not initialized");
+	// the lock on the cache entry is held until the end of the build
+	let cache_entry = BindingsCache::from_env()
+		.map(|cache| cache.entry(&opencv_header_dir, &opencv_module_header_dir, &opencv, modules))
+		.transpose()?;
+	if let Some(pregenerated) = PregeneratedBindings::from_env() {
+		eprintln!("=== Using pregenerated bindings from: {}", pregenerated.dir().display());
+		pregenerated.install(&OUT_DIR, &opencv.version, modules)?;
+		// cargo scans the whole directory for changes
+		println!("cargo:rerun-if-changed={}", pregenerated.dir().display()); // replace with cargo:: syntax when MSRV is 1.77
+	} else if let Some(cached) = cache_entry.as_ref().and_then(CacheEntry::bindings) {
+		eprintln!("=== Using cached bindings from: {}", cached.dir().display());
+		cached.install(&OUT_DIR, &opencv.version, modules)?;
+	} else {
+		let binding_generator = BindingGenerator::new(build_script_path);
+		binding_generator.generate_wrapper(&opencv_header_dir, &opencv, &ffi_export_suffix)?;
+		if let Some(cache_entry) = &cache_entry {
+			cache_entry.store_bindings(&OUT_DIR, &opencv.version, modules)?;
+		}
+	}
 	let cc = build_compiler(&opencv, &ffi_export_suffix);
-	build_wrapper(cc);
+	build_wrapper(cc, cache_entry.as_ref())?;
 	// -l linker args should be emitted after -l static
 	opencv.emit_cargo_metadata();
 	Ok(())
@@ -0,0 +1,426 @@
+use std::ffi::OsStr;
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use std::{env, fs, process};
+
+use semver::Version;
+
+use super::generator::additional_include_dirs;
+use super::pregenerated::{export_bindings, is_cpp_file, PregeneratedBindings};
+use super::{Library, Result, MANIFEST_DIR, SRC_CPP_DIR, TARGET_ENV_MSVC};
+
+/// Increment when the layout of the cache or the set of the hashed inputs changes
+const CACHE_FORMAT_VERSION: u32 = 3;
+
+/// Persistent cache of the generated bindings and the compiled C++ glue library shared between the builds in different target
+/// dirs, enabled by the `OPENCV_BINDINGS_CACHE_DIR` environment var
+///
+/// Entries are content-addressed: the bindings are keyed by the hash of the OpenCV headers and include dirs, the enabled module
+/// list, the target, the crate and binding generator versions and the build script sources, the library is keyed by the hash of the C++ sources it's
+/// compiled from together with the compiler and its flags. Nothing is ever evicted from the cache automatically.
+pub struct BindingsCache {
+	dir: PathBuf,
+}
+
+impl BindingsCache {
+	pub fn from_env() -> Option<Self> {
+		env::var_os("OPENCV_BINDINGS_CACHE_DIR")
+			.filter(|dir| !dir.is_empty())
+			.map(|dir| Self { dir: PathBuf::from(dir) })
+	}
+
+	/// Returns the cache entry for the specified build inputs, the entry is locked until it's dropped, so the concurrent builds
+	/// with the same inputs wait for each other instead of generating the same bindings in parallel
+	pub fn entry(
+		&self,
+		opencv_header_dir: &Path,
+		opencv_module_header_dir: &Path,
+		opencv: &Library,
+		modules: &[String],
+	) -> Result<CacheEntry> {
+		let start = Instant::now();
+		let mut headers_hasher = ContentHasher::new();
+		headers_hasher.update_dir(opencv_module_header_dir, |_| true)?;
+		let headers_hash = headers_hasher.finish();
+
+		let mut bindings_hasher = ContentHasher::new();
+		bindings_hasher.update(&CACHE_FORMAT_VERSION.to_le_bytes());
+		bindings_hasher.update(&headers_hash.to_le_bytes());
+		bindings_hasher.update(opencv.version.to_string().as_bytes());
+		bindings_hasher.update(env::var("TARGET").unwrap_or_default().as_bytes());
+		for include_dir in additional_include_dirs(opencv_header_dir, opencv) {
+			bindings_hasher.update(include_dir.as_os_str().to_string_lossy().as_bytes());
+		}
+		bindings_hasher.update(env::var("CARGO_PKG_VERSION").unwrap_or_default().as_bytes());
+		bindings_hasher.update(build_script_unit()?.as_bytes());
+		for module in modules {
+			bindings_hasher.update(module.as_bytes());
+		}
+		// the build script post-processes the generated bindings, the sources (unlike the binary) are the same in all target dirs
+		bindings_hasher.update_file(&MANIFEST_DIR.join("build.rs"))?;
+		bindings_hasher.update_dir(&MANIFEST_DIR.join("build"), |path| path.extension() == Some(OsStr::new("rs")))?;
+		let key = bindings_hasher.finish_hex();
+		eprintln!("=== Bindings cache key: {key} (computed in {:?})", start.elapsed());
+
+		fs::create_dir_all(&self.dir)?;
+		let lock = CacheLock::acquire(self.dir.join(format!("{key}.lock")))?;
+		Ok(CacheEntry {
+			dir: self.dir.join(key),
+			headers_hash,
+			_lock: lock,
+		})
+	}
+}
+
+/// Name of the directory of the build script binary, e.g. `opencv-0123456789abcdef`
+///
+/// Cargo derives the hash part from the resolved versions of the build script dependencies, so it changes together with the
+/// version of `opencv-binding-generator` (and the compiler) without having to pin it in Cargo.toml.
+fn build_script_unit() -> Result<String> {
+	let build_script_path = env::current_exe()?;
+	build_script_path
+		.parent()
+		.and_then(Path::file_name)
+		.map(|name| name.to_string_lossy().into_owned())
+		.ok_or_else(|| format!("Can't get the build script dir from: {}", build_script_path.display()).into())
+}
+
+pub struct CacheEntry {
+	dir: PathBuf,
+	headers_hash: u64,
+	_lock: CacheLock,
+}
+
+impl CacheEntry {
+	fn bindings_dir(&self) -> PathBuf {
+		self.dir.join("bindings")
+	}
+
+	/// Returns the cached bindings if they were stored by one of the previous builds
+	pub fn bindings(&self) -> Option<PregeneratedBindings> {
+		Some(PregeneratedBindings::new(self.bindings_dir())).filter(PregeneratedBindings::is_complete)
+	}
+
+	/// Stores the bindings generated in `out_dir` for the future builds
+	pub fn store_bindings(&self, out_dir: &Path, opencv_version: &Version, modules: &[String]) -> Result<()> {
+		let bindings_dir = self.bindings_dir();
+		eprintln!("=== Storing generated bindings in the cache: {}", bindings_dir.display());
+		export_bindings(out_dir, &bindings_dir, opencv_version, modules)
+	}
+
+	/// Returns the cache slot for the C++ glue library compiled with `cc` from the `sources`
+	pub fn library(&self, cc: &cc::Build, sources: &[PathBuf], out_dir: &Path) -> Result<CachedLibrary> {
+		let mut hasher = ContentHasher::new();
+		hasher.update(&CACHE_FORMAT_VERSION.to_le_bytes());
+		hasher.update(&self.headers_hash.to_le_bytes());
+		hasher.update(env::var("TARGET").unwrap_or_default().as_bytes());
+		for source in sources {
+			hasher.update(source.file_name().unwrap_or_default().to_string_lossy().as_bytes());
+			hasher.update_file(source)?;
+		}
+		// headers included by the sources, the generated ones are placed at the top level of OUT_DIR, its subdirectories contain
+		// unrelated files like the vendored OpenCV build
+		hasher.update_dir_top_level(out_dir, is_cpp_file)?;
+		hasher.update_dir(&SRC_CPP_DIR, is_cpp_file)?;
+
+		let compiler = cc.get_compiler();
+		hasher.update(compiler.path().as_os_str().to_string_lossy().as_bytes());
+		// OUT_DIR is different for every target dir, but the contents of the included files are already hashed
+		let out_dir = out_dir.to_string_lossy();
+		for arg in compiler.args() {
+			hasher.update(arg.to_string_lossy().replace(&*out_dir, "$OUT_DIR").as_bytes());
+		}
+		if let Ok(version) = compiler.to_command().arg("--version").output() {
+			hasher.update(&version.stdout);
+			hasher.update(&version.stderr);
+		}
+		Ok(CachedLibrary {
+			path: self
+				.dir
+				.join(format!("ocvrs-{}", hasher.finish_hex()))
+				.join(library_file_name()),
+		})
+	}
+}
+
+/// Cache slot for the compiled C++ glue library
+pub struct CachedLibrary {
+	path: PathBuf,
+}
+
+impl CachedLibrary {
+	/// Copies the cached library into `out_dir` and emits the same linking instructions as `cc::Build::compile()`, returns
+	/// `false` if the library is not in the cache yet
+	pub fn restore(&self, out_dir: &Path) -> Result<bool> {
+		if !self.path.is_file() {
+			return Ok(false);
+		}
+		eprintln!("=== Using cached C++ glue library: {}", self.path.display());
+		fs::copy(&self.path, out_dir.join(library_file_name()))?;
+		println!("cargo:rustc-link-lib=static=ocvrs"); // replace with cargo:: syntax when MSRV is 1.77
+		println!("cargo:rustc-link-search=native={}", out_dir.display()); // replace with cargo:: syntax when MSRV is 1.77
+		if let Some(stdlib) = cpp_link_stdlib() {
+			println!("cargo:rustc-link-lib={stdlib}"); // replace with cargo:: syntax when MSRV is 1.77
+		}
+		Ok(true)
+	}
+
+	/// Stores the library compiled in `out_dir` for the future builds
+	pub fn store(&self, out_dir: &Path) -> Result<()> {
+		let dir = self.path.parent().expect("Cached library path always has a parent");
+		fs::create_dir_all(dir)?;
+		// copy and rename so that the concurrent builds with a different bindings key never see a partially copied file
+		let tmp_path = self.path.with_extension(format!("tmp{}", process::id()));
+		fs::copy(out_dir.join(library_file_name()), &tmp_path)?;
+		fs::rename(&tmp_path, &self.path)?;
+		Ok(())
+	}
+}
+
+fn library_file_name() -> &'static str {
+	if *TARGET_ENV_MSVC {
+		"ocvrs.lib"
+	} else {
+		"libocvrs.a"
+	}
+}
+
+/// C++ standard library to link, follows the defaults of `cc::Build::cpp_link_stdlib()`
+fn cpp_link_stdlib() -> Option<String> {
+	if let Ok(stdlib) = env::var("CXXSTDLIB") {
+		return Some(stdlib).filter(|stdlib| !stdlib.is_empty());
+	}
+	let target = env::var("TARGET").unwrap_or_default();
+	if target.contains("msvc") {
+		None
+	} else if ["apple", "freebsd", "openbsd", "aix", "linux-ohos", "-wasi"]
+		.iter()
+		.any(|os| target.contains(os))
+	{
+		Some("c++".to_string())
+	} else if target.contains("android") {
+		Some("c++_shared".to_string())
+	} else {
+		Some("stdc++".to_string())
+	}
+}
+
+/// Cross-process lock on a file, it's held by the OS file lock (`flock()` or `LockFileEx()`) which is released automatically
+/// when the process exits (even if it's killed), so the lock can never be left stale
+///
+/// The lock file itself is never removed, removing it while another process waits on it would allow a third process to lock
+/// a new file with the same name at the same time.
+struct CacheLock {
+	_file: File,
+}
+
+impl CacheLock {
+	fn acquire(path: PathBuf) -> Result<Self> {
+		let file = OpenOptions::new()
+			.read(true)
+			.write(true)
+			.create(true)
+			.truncate(false)
+			.open(&path)
+			.map_err(|e| format!("Can't create bindings cache lock: {}, error: {e}", path.display()))?;
+		let start = Instant::now();
+		let locked = file_lock::try_lock_exclusive(&file)
+			.map_err(|e| format!("Can't lock bindings cache lock: {}, error: {e}", path.display()))?;
+		if !locked {
+			eprintln!("=== Waiting for bindings cache lock: {}", path.display());
+			file_lock::lock_exclusive(&file)
+				.map_err(|e| format!("Can't lock bindings cache lock: {}, error: {e}", path.display()))?;
+			eprintln!("=== Acquired bindings cache lock after {:?}", start.elapsed());
+		}
+		Ok(Self { _file: file })
+	}
+}
+
+#[cfg(unix)]
+mod file_lock {
+	use std::ffi::c_int;
+	use std::fs::File;
+	use std::io;
+	use std::os::unix::io::AsRawFd;
+
+	const LOCK_EX: c_int = 2;
+	const LOCK_NB: c_int = 4;
+
+	extern "C" {
+		fn flock(fd: c_int, operation: c_int) -> c_int;
+	}
+
+	fn flock_file(file: &File, operation: c_int) -> io::Result<()> {
+		loop {
+			if unsafe { flock(file.as_raw_fd(), operation) } == 0 {
+				return Ok(());
+			}
+			let e = io::Error::last_os_error();
+			if e.kind() != io::ErrorKind::Interrupted {
+				return Err(e);
+			}
+		}
+	}
+
+	/// Returns `false` if the file is already locked by another process
+	pub fn try_lock_exclusive(file: &File) -> io::Result<bool> {
+		match flock_file(file, LOCK_EX | LOCK_NB) {
+			Ok(()) => Ok(true),
+			Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(false),
+			Err(e) => Err(e),
+		}
+	}
+
+	pub fn lock_exclusive(file: &File) -> io::Result<()> {
+		flock_file(file, LOCK_EX)
+	}
+}
+
+#[cfg(windows)]
+mod file_lock {
+	use std::ffi::c_void;
+	use std::fs::File;
+	use std::os::windows::io::AsRawHandle;
+	use std::{io, mem};
+
+	const LOCKFILE_FAIL_IMMEDIATELY: u32 = 0x1;
+	const LOCKFILE_EXCLUSIVE_LOCK: u32 = 0x2;
+	const ERROR_LOCK_VIOLATION: i32 = 33;
+
+	#[repr(C)]
+	struct Overlapped {
+		internal: usize,
+		internal_high: usize,
+		offset: u32,
+		offset_high: u32,
+		event: *mut c_void,
+	}
+
+	#[link(name = "kernel32")]
+	extern "system" {
+		fn LockFileEx(
+			file: *mut c_void,
+			flags: u32,
+			reserved: u32,
+			bytes_to_lock_low: u32,
+			bytes_to_lock_high: u32,
+			overlapped: *mut Overlapped,
+		) -> i32;
+	}
+
+	fn lock_file(file: &File, flags: u32) -> io::Result<()> {
+		let mut overlapped: Overlapped = unsafe { mem::zeroed() };
+		if unsafe { LockFileEx(file.as_raw_handle().cast(), flags, 0, u32::MAX, u32::MAX, &mut overlapped) } != 0 {
+			Ok(())
+		} else {
+			Err(io::Error::last_os_error())
+		}
+	}
+
+	/// Returns `false` if the file is already locked by another process
+	pub fn try_lock_exclusive(file: &File) -> io::Result<bool> {
+		match lock_file(file, LOCKFILE_EXCLUSIVE_LOCK | LOCKFILE_FAIL_IMMEDIATELY) {
+			Ok(()) => Ok(true),
+			Err(e) if e.raw_os_error() == Some(ERROR_LOCK_VIOLATION) => Ok(false),
+			Err(e) => Err(e),
+		}
+	}
+
+	pub fn lock_exclusive(file: &File) -> io::Result<()> {
+		lock_file(file, LOCKFILE_EXCLUSIVE_LOCK)
+	}
+}
+
+/// 64-bit FNV-1a, unlike `DefaultHasher` its output is guaranteed to stay the same between the Rust versions
+struct ContentHasher(u64);
+
+impl ContentHasher {
+	fn new() -> Self {
+		Self(0xcbf2_9ce4_8422_2325)
+	}
+
+	fn write(&mut self, bytes: &[u8]) {
+		for &b in bytes {
+			self.0 ^= u64::from(b);
+			self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+		}
+	}
+
+	/// Hashes the length-prefixed value so that the boundaries between the consecutive values are unambiguous
+	fn update(&mut self, bytes: &[u8]) {
+		self.write(&(bytes.len() as u64).to_le_bytes());
+		self.write(bytes);
+	}
+
+	fn update_file(&mut self, path: &Path) -> Result<()> {
+		let mut file = File::open(path).map_err(|e| format!("Can't read file for hashing: {}, error: {e}", path.display()))?;
+		self.write(&file.metadata()?.len().to_le_bytes());
+		let mut buf = [0; 64 * 1024];
+		loop {
+			match file.read(&mut buf) {
+				Ok(0) => return Ok(()),
+				Ok(read) => self.write(&buf[..read]),
+				Err(e) if e.kind() == ErrorKind::Interrupted => {}
+				Err(e) => return Err(e.into()),
+			}
+		}
+	}
+
+	/// Hashes the relative paths and the contents of the files under `dir` (recursively) matching the `predicate`
+	fn update_dir(&mut self, dir: &Path, mut predicate: impl FnMut(&Path) -> bool) -> Result<()> {
+		let mut files = vec![];
+		collect_files(dir, &mut predicate, &mut files)?;
+		files.sort_unstable();
+		for file in files {
+			let relative = file.strip_prefix(dir).unwrap_or(&file);
+			// normalize the separators so that the same headers produce the same key on all platforms
+			let relative = relative.iter().map(OsStr::to_string_lossy).collect::<Vec<_>>().join("/");
+			self.update(relative.as_bytes());
+			self.update_file(&file)?;
+		}
+		Ok(())
+	}
+
+	/// Same as [ContentHasher::update_dir], but only hashes the files directly in `dir` and skips the subdirectories
+	fn update_dir_top_level(&mut self, dir: &Path, mut predicate: impl FnMut(&Path) -> bool) -> Result<()> {
+		let mut files = dir
+			.read_dir()?
+			.flatten()
+			.filter(|entry| entry.file_type().map_or(false, |typ| typ.is_file()))
+			.map(|entry| entry.path())
+			.filter(|path| predicate(path))
+			.collect::<Vec<_>>();
+		files.sort_unstable();
+		for file in files {
+			self.update(file.file_name().unwrap_or_default().to_string_lossy().as_bytes());
+			self.update_file(&file)?;
+		}
+		Ok(())
+	}
+
+	fn finish(&self) -> u64 {
+		self.0
+	}
+
+	fn finish_hex(&self) -> String {
+		format!("{:016x}", self.0)
+	}
+}
+
+fn collect_files(dir: &Path, predicate: &mut impl FnMut(&Path) -> bool, out: &mut Vec<PathBuf>) -> Result<()> {
+	for entry in dir.read_dir()?.flatten() {
+		let path = entry.path();
+		match entry.file_type() {
+			Ok(typ) if typ.is_dir() => collect_files(&path, predicate, out)?,
+			Ok(_) => {
+				if predicate(&path) {
+					out.push(path);
+				}
+			}
+			Err(_) => {}
+		}
+	}
+	Ok(())
+}
@@ -24,6 +24,16 @@ impl LinkLib {
 		) // replace with cargo:: syntax when MSRV is 1.77
 	}
 
+	/// Like [LinkLib::emit_cargo_rustc_link], but keeps the static linkage, used for the libraries built by the crate itself
+	#[inline]
+	pub fn emit_cargo_rustc_link_keep_static(&self) -> String {
+		format!(
+			"cargo:rustc-link-lib={}{}",
+			self.0.as_cargo_rustc_link_spec(),
+			self.1
+		) // replace with cargo:: syntax when MSRV is 1.77
+	}
+
 	/// Returns Some(new_file_name) if some parts of the filename were removed, None otherwise
 	pub fn cleanup_lib_filename(filename: &OsStr) -> Option<&OsStr> {
 		let mut new_filename = filename;
@@ -109,6 +119,7 @@ pub struct CmakeProbe<'r> {
 	src_dir: &'r Path,
 	package_name: &'r str,
 	toolchain: Option<&'r Path>,
+	prefix_path: Option<&'r Path>,
 	is_release: bool,
 }
 
@@ -127,10 +138,17 @@ impl<'r> CmakeProbe<'r> {
 			src_dir,
 			package_name,
 			toolchain,
+			prefix_path: None,
 			is_release,
 		}
 	}
 
+	/// Adds the directory to search for the OpenCV package in before the system ones
+	pub fn with_prefix_path(mut self, prefix_path: &'r Path) -> Self {
+		self.prefix_path = Some(prefix_path);
+		self
+	}
+
 	fn prepare(&self) -> Result<()> {
 		self.cleanup()?;
 		fs::create_dir(&self.build_dir)?;
@@ -157,6 +175,12 @@ impl<'r> CmakeProbe<'r> {
 				toolchain.to_str().expect("Non-UTF-8 toolchain location")
 			));
 		}
+		if let Some(prefix_path) = self.prefix_path {
+			out.arg(format!(
+				"-DCMAKE_PREFIX_PATH={}",
+				prefix_path.to_str().expect("Non-UTF-8 prefix path location")
+			));
+		}
 		if self.is_release {
 			out.arg("-DCMAKE_BUILD_TYPE=Release");
 		} else {
@@ -8,6 +8,7 @@ use collector::Collector;
 use opencv_binding_generator::{Generator, IteratorExt};
 
 use super::docs::transfer_bindings_to_docs;
+use super::pregenerated::export_bindings;
 use super::{files_with_predicate, Library, Result, MODULES, OUT_DIR, SRC_CPP_DIR, SRC_DIR};
 
 #[path = "generator/collector.rs"]
@@ -57,16 +58,17 @@ impl BindingGenerator {
 			transfer_bindings_to_docs(&OUT_DIR, &target_docs_dir);
 		}
 
+		if let Some(export_dir) = env::var_os("OPENCV_PREGENERATED_BINDINGS_EXPORT_DIR").filter(|dir| !dir.is_empty()) {
+			let export_dir = PathBuf::from(export_dir);
+			eprintln!("=== Exporting generated bindings into: {}", export_dir.display());
+			export_bindings(&OUT_DIR, &export_dir, &opencv.version, modules)?;
+		}
+
 		Ok(())
 	}
 
 	fn run(&self, modules: &[String], opencv_header_dir: &Path, opencv: &Library) -> Result<()> {
-		let additional_include_dirs = opencv
-			.include_paths
-			.iter()
-			.filter(|&include_path| include_path != opencv_header_dir)
-			.map(|path| path.as_path())
-			.collect::<Vec<_>>();
+		let additional_include_dirs = additional_include_dirs(opencv_header_dir, opencv);
 
 		let gen = Generator::new(opencv_header_dir, &additional_include_dirs, &SRC_CPP_DIR);
 		if !gen.is_clang_loaded() {
@@ -124,6 +126,16 @@ impl BindingGenerator {
 	}
 }
 
+/// Include paths of the OpenCV library that are passed to the generator in addition to the main header dir
+pub fn additional_include_dirs<'l>(opencv_header_dir: &Path, opencv: &'l Library) -> Vec<&'l Path> {
+	opencv
+		.include_paths
+		.iter()
+		.filter(|&include_path| include_path != opencv_header_dir)
+		.map(|path| path.as_path())
+		.collect()
+}
+
 pub struct Jobserver {
 	client: jobserver::Client,
 	reacquire_token_on_drop: bool,
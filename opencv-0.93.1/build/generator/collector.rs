@@ -123,7 +123,7 @@ impl<'r> Collector<'r> {
 		// module file is include!d (as opposed to connecting the module with `mod` from the parent module).
 		// The same doesn't apply to `sys` and `types` below because they don't contain top-level comments.
 		writeln!(module_rs, "pub mod {module} {{")?;
-		copy_indent(BufReader::new(File::open(&module_src_file)?), &mut module_rs, "\t")?;
+		copy_indent_patch_callbacks(BufReader::new(File::open(&module_src_file)?), &mut module_rs, "\t", module)?;
 		self.write_use_manual(&mut module_rs, module)?;
 		writeln!(module_rs, "}}")?;
 		let _ = fs::remove_file(module_src_file);
@@ -200,6 +200,12 @@ impl<'r> Collector<'r> {
 			r#"#[no_mangle] unsafe extern "C" fn ocvrs_create_byte_string{}(v: *const u8, len: size_t) -> *mut Vec<u8> {{ crate::templ::ocvrs_create_byte_string(v, len) }}"#,
 			self.ffi_export_suffix
 		)?;
+		write!(hub_rs, "\t")?;
+		writeln!(
+			hub_rs,
+			r#"#[no_mangle] unsafe extern "C" fn ocvrs_create_error{}(e: *const crate::sys::Exception) -> *mut crate::Error {{ crate::templ::ocvrs_create_error(e) }}"#,
+			self.ffi_export_suffix
+		)?;
 		writeln!(hub_rs, "}}")?;
 
 		Ok(())
@@ -247,6 +253,133 @@ fn copy_indent(mut read: impl BufRead, write: &mut impl Write, indent: &str) ->
 	Ok(())
 }
 
+/// How the generated function handles the closures passed to OpenCV, see `crate::callbacks`
+enum CallbackPatch {
+	/// The closure is stored in the callback registry under the key produced by the expression
+	Register(&'static str),
+	/// The closure is called exactly once and dropped afterward
+	Once,
+	/// The statement releasing the registered closures is run after the successful call
+	Release(&'static str),
+}
+
+impl CallbackPatch {
+	/// Lines of the generated function that must be rewritten for the patch to apply, see `PATCHED_*`
+	fn required(&self) -> u8 {
+		match self {
+			Self::Register(_) => PATCHED_USERDATA_ARG | PATCHED_RESULT,
+			Self::Once => PATCHED_CALLBACK_ARG,
+			Self::Release(_) => PATCHED_RESULT,
+		}
+	}
+}
+
+const PATCHED_CALLBACK_ARG: u8 = 1;
+const PATCHED_USERDATA_ARG: u8 = 1 << 1;
+const PATCHED_RESULT: u8 = 1 << 2;
+
+/// (module, function name, patch)
+static CALLBACK_PATCHES: &[(&str, &str, CallbackPatch)] = &[
+	(
+		"core",
+		"enqueue_host_callback",
+		CallbackPatch::Once,
+	),
+	(
+		"highgui",
+		"create_trackbar",
+		CallbackPatch::Register("crate::callbacks::CallbackKey::Trackbar { window: winname.to_string_lossy().into_owned(), trackbar: trackbarname.to_string_lossy().into_owned() }"),
+	),
+	(
+		"highgui",
+		"destroy_all_windows",
+		CallbackPatch::Release("crate::callbacks::release_all_windows();"),
+	),
+	(
+		"highgui",
+		"destroy_window",
+		CallbackPatch::Release("crate::callbacks::release_window(&winname.to_string_lossy());"),
+	),
+	(
+		"highgui",
+		"set_mouse_callback",
+		CallbackPatch::Register("crate::callbacks::CallbackKey::MouseCallback { window: winname.to_string_lossy().into_owned() }"),
+	),
+	(
+		"highgui",
+		"set_opengl_draw_callback",
+		CallbackPatch::Register("crate::callbacks::CallbackKey::OpenGlDrawCallback { window: winname.to_string_lossy().into_owned() }"),
+	),
+];
+
+/// Same as `copy_indent`, but also rewrites the functions from `CALLBACK_PATCHES` so that the closures passed to them are
+/// properly dropped
+///
+/// Fails if any of the patches for the `module` doesn't match the generated code, otherwise the change in the generated code
+/// would silently bring back the leak (or the double free for `CallbackPatch::Once`).
+fn copy_indent_patch_callbacks(read: impl BufRead, write: &mut impl Write, indent: &str, module: &str) -> Result<()> {
+	const CALLBACKS_ARG: &str = " in callbacks => ";
+	const RESULT_LINE: &str = "let ret = ret.into_result()?;";
+
+	let mut applied = vec![0; CALLBACK_PATCHES.len()];
+	let mut patch_idx = None;
+	for line in read.lines() {
+		let mut line = line?;
+		let trimmed = line.trim_start();
+		let fn_name = trimmed
+			.strip_prefix("pub fn ")
+			.or_else(|| trimmed.strip_prefix("fn "))
+			.and_then(|decl| decl.split_once('('))
+			.map(|(name, _)| name);
+		if let Some(fn_name) = fn_name {
+			patch_idx = CALLBACK_PATCHES
+				.iter()
+				.position(|(patch_module, patch_fn_name, _)| *patch_module == module && *patch_fn_name == fn_name);
+		}
+		let mut after = None;
+		if let Some(patch_idx) = patch_idx {
+			let (patched, new_line) = match &CALLBACK_PATCHES[patch_idx].2 {
+				CallbackPatch::Register(key) if trimmed.starts_with("userdata_arg!(") && trimmed.contains(CALLBACKS_ARG) => (
+					PATCHED_USERDATA_ARG,
+					Some(line.replacen(CALLBACKS_ARG, &format!(" in callbacks[{key}] => "), 1)),
+				),
+				CallbackPatch::Register(_) if trimmed == RESULT_LINE => {
+					after = Some("callbacks.register();");
+					(PATCHED_RESULT, None)
+				}
+				CallbackPatch::Once if trimmed.starts_with("callback_arg!(") && trimmed.contains(CALLBACKS_ARG) => (
+					PATCHED_CALLBACK_ARG,
+					Some(line.replacen(CALLBACKS_ARG, " in callbacks once => ", 1)),
+				),
+				CallbackPatch::Release(stmt) if trimmed == RESULT_LINE => {
+					after = Some(*stmt);
+					(PATCHED_RESULT, None)
+				}
+				_ => (0, None),
+			};
+			applied[patch_idx] |= patched;
+			if let Some(new_line) = new_line {
+				line = new_line;
+			}
+		}
+		writeln!(write, "{indent}{line}")?;
+		if let Some(after) = after {
+			let line_indent = &line[..line.len() - line.trim_start().len()];
+			writeln!(write, "{indent}{line_indent}{after}")?;
+		}
+	}
+	for ((patch_module, fn_name, patch), applied) in CALLBACK_PATCHES.iter().zip(applied) {
+		if *patch_module == module && applied != patch.required() {
+			return Err(format!(
+				"Callback patch for {module}::{fn_name} doesn't match the generated code, update CALLBACK_PATCHES in {}",
+				file!()
+			)
+			.into());
+		}
+	}
+	Ok(())
+}
+
 fn write_has_module(write: &mut impl Write, module: &str) -> Result<()> {
 	Ok(writeln!(write, "#[cfg(ocvrs_has_module_{module})]")?)
 }
@@ -7,7 +7,9 @@ use std::{env, fmt, iter};
 use dunce::canonicalize;
 use semver::Version;
 
-use super::cmake_probe::{CmakeProbe, LinkLib, LinkSearch};
+use super::cmake_probe::{CmakeProbe, LinkLib, LinkSearch, ProbeResult};
+use super::report::{ProbeOutcome, ProbeReport};
+use super::vendored::VendoredBuild;
 use super::{get_version_from_headers, Result, MANIFEST_DIR, OUT_DIR, TARGET_VENDOR_APPLE};
 
 struct PackageName;
@@ -339,6 +341,20 @@ impl Library {
 			toolchain,
 			env::var_os("PROFILE").map_or(false, |p| p == "release"),
 		);
+		let probe_result = Self::probe_cmake_result(&cmake, ninja_bin)?;
+
+		let mut cargo_metadata = Vec::with_capacity(probe_result.link_paths.len() + probe_result.link_libs.len());
+		cargo_metadata.extend(Self::process_link_paths(link_paths, probe_result.link_paths));
+		cargo_metadata.extend(Self::process_link_libs(link_libs, probe_result.link_libs));
+
+		Ok(Self {
+			include_paths: Self::process_env_var_list(include_paths, probe_result.include_paths),
+			version: probe_result.version.unwrap_or_else(|| Version::new(0, 0, 0)),
+			cargo_metadata,
+		})
+	}
+
+	fn probe_cmake_result(cmake: &CmakeProbe, ninja_bin: Option<&Path>) -> Result<ProbeResult> {
 		let mut probe_result = cmake
 			.probe_ninja(ninja_bin)
 			.or_else(|e| {
@@ -353,10 +369,44 @@ impl Library {
 		if probe_result.version.is_none() {
 			probe_result.version = Self::version_from_include_paths(&probe_result.include_paths);
 		}
+		Ok(probe_result)
+	}
+
+	/// Builds OpenCV from source and probes the resulting installation with cmake, the libraries from the build are linked
+	/// statically
+	pub fn probe_vendored(
+		vendored: &VendoredBuild,
+		include_paths: Option<EnvList>,
+		link_paths: Option<EnvList>,
+		link_libs: Option<EnvList>,
+	) -> Result<Self> {
+		vendored.build()?;
+		eprintln!(
+			"=== Probing OpenCV library built from source at: {}",
+			vendored.install_dir().display()
+		);
+
+		let src_dir = MANIFEST_DIR.join("cmake");
+		let package_name = PackageName::cmake();
+		let cmake = CmakeProbe::new(
+			Some(vendored.cmake_bin().to_path_buf()),
+			&OUT_DIR,
+			&src_dir,
+			package_name.as_ref(),
+			None,
+			true,
+		)
+		.with_prefix_path(vendored.install_dir());
+		let mut probe_result = Self::probe_cmake_result(&cmake, None)?;
+		vendored.mark_static(&probe_result.link_paths, &mut probe_result.link_libs);
 
 		let mut cargo_metadata = Vec::with_capacity(probe_result.link_paths.len() + probe_result.link_libs.len());
 		cargo_metadata.extend(Self::process_link_paths(link_paths, probe_result.link_paths));
-		cargo_metadata.extend(Self::process_link_libs(link_libs, probe_result.link_libs));
+		cargo_metadata.extend(
+			Self::process_env_var_list(link_libs, probe_result.link_libs)
+				.into_iter()
+				.map(|l| l.emit_cargo_rustc_link_keep_static()),
+		);
 
 		Ok(Self {
 			include_paths: Self::process_env_var_list(include_paths, probe_result.include_paths),
@@ -440,7 +490,12 @@ impl Library {
 		)
 	}
 
-	pub fn probe_system(include_paths: Option<EnvList>, link_paths: Option<EnvList>, link_libs: Option<EnvList>) -> Result<Self> {
+	pub fn probe_system(
+		include_paths: Option<EnvList>,
+		link_paths: Option<EnvList>,
+		link_libs: Option<EnvList>,
+		report: &mut ProbeReport,
+	) -> Result<Self> {
 		let probe_paths = || Self::probe_from_paths(include_paths, link_paths, link_libs);
 		let probe_pkg_config = || Self::probe_pkg_config(include_paths, link_paths, link_libs);
 		let probe_cmake = || Self::probe_cmake(include_paths, link_paths, link_libs, None, None, None);
@@ -515,14 +570,20 @@ impl Library {
 					Ok(lib) => {
 						out = Some(lib);
 						eprintln!("=== Successfully probed using: {name}");
+						report.add_probe(name, ProbeOutcome::Success);
 						break;
 					}
 					Err(e) => {
 						eprintln!("=== Can't probe using: {name}, continuing with other methods because: {e}");
+						report.add_probe(name, ProbeOutcome::Failed(e.to_string()));
 					}
 				}
 			} else {
 				eprintln!("=== Skipping probe: {name} because it's disabled using OPENCV_DISABLE_PROBES");
+				report.add_probe(
+					name,
+					ProbeOutcome::Skipped("disabled using OPENCV_DISABLE_PROBES".to_string()),
+				);
 			}
 		}
 		out.ok_or_else(|| {
@@ -536,14 +597,25 @@ impl Library {
 		})
 	}
 
-	pub fn probe() -> Result<Self> {
+	/// Finds the OpenCV library, all the attempted probes are recorded in the `report`
+	pub fn probe(report: &mut ProbeReport) -> Result<Self> {
 		let include_paths = env::var("OPENCV_INCLUDE_PATHS").ok();
 		let include_paths = include_paths.as_deref().map(EnvList::from);
 		let link_paths = env::var("OPENCV_LINK_PATHS").ok();
 		let link_paths = link_paths.as_deref().map(EnvList::from);
 		let link_libs = env::var("OPENCV_LINK_LIBS").ok();
 		let link_libs = link_libs.as_deref().map(EnvList::from);
-		Self::probe_system(include_paths, link_paths, link_libs)
+		if let Some(vendored) = VendoredBuild::from_env() {
+			let out = Self::probe_vendored(&vendored, include_paths, link_paths, link_libs);
+			report.add_probe(
+				"vendored",
+				out.as_ref()
+					.map_or_else(|e| ProbeOutcome::Failed(e.to_string()), |_| ProbeOutcome::Success),
+			);
+			out
+		} else {
+			Self::probe_system(include_paths, link_paths, link_libs, report)
+		}
 	}
 
 	pub fn emit_cargo_metadata(&self) {
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use semver::Version;
+
+use super::{files_with_extension, files_with_predicate, Result};
+
+/// Name of the file describing the pregenerated bindings, it's placed in the root of the bindings dir
+const MANIFEST_FILE: &str = "ocvrs-bindings.txt";
+
+/// Bindings generated in one of the previous builds and stored outside OUT_DIR, they are used instead of running the binding
+/// generator so that the build doesn't need libclang
+///
+/// The directory layout is the same as the one produced in OUT_DIR by the binding generator: `opencv/*.rs` files with the Rust
+/// bindings, `*.cpp` and `*.hpp` files with the C++ glue in the root and the manifest file describing the OpenCV version and the
+/// module list that the bindings were generated for.
+pub struct PregeneratedBindings {
+	dir: PathBuf,
+}
+
+impl PregeneratedBindings {
+	/// Returns the bindings from the dir specified in the `OPENCV_PREGENERATED_BINDINGS` environment var
+	pub fn from_env() -> Option<Self> {
+		env::var_os("OPENCV_PREGENERATED_BINDINGS")
+			.filter(|dir| !dir.is_empty())
+			.map(|dir| Self::new(PathBuf::from(dir)))
+	}
+
+	pub fn new(dir: PathBuf) -> Self {
+		Self { dir }
+	}
+
+	/// Returns `true` if the dir contains the manifest, it's written after all other files when exporting
+	pub fn is_complete(&self) -> bool {
+		self.dir.join(MANIFEST_FILE).is_file()
+	}
+
+	pub fn dir(&self) -> &Path {
+		&self.dir
+	}
+
+	/// Checks that the bindings were generated for the specified OpenCV version and contain all the `modules`, then copies
+	/// them to `out_dir`
+	pub fn install(&self, out_dir: &Path, opencv_version: &Version, modules: &[String]) -> Result<()> {
+		let manifest = Manifest::read(&self.dir.join(MANIFEST_FILE))?;
+		manifest.validate(opencv_version, modules)?;
+
+		let non_dll_files = files_with_predicate(out_dir, |p| {
+			p.extension().map_or(true, |ext| !ext.eq_ignore_ascii_case("dll"))
+		})?;
+		for path in non_dll_files {
+			let _ = fs::remove_file(path);
+		}
+		let out_module_dir = out_dir.join("opencv");
+		if out_module_dir.exists() {
+			for path in files_with_extension(&out_module_dir, "rs")? {
+				let _ = fs::remove_file(path);
+			}
+		}
+		copy_bindings(&self.dir, out_dir)
+	}
+}
+
+/// Copies the bindings generated in `out_dir` to `target_dir` and writes the manifest for them, the result can later be used
+/// as [PregeneratedBindings]
+pub fn export_bindings(out_dir: &Path, target_dir: &Path, opencv_version: &Version, modules: &[String]) -> Result<()> {
+	if target_dir.exists() {
+		// the manifest is written last so that the partially copied bindings are not considered complete
+		let _ = fs::remove_file(target_dir.join(MANIFEST_FILE));
+		for path in files_with_predicate(target_dir, is_cpp_file)? {
+			let _ = fs::remove_file(path);
+		}
+		let target_module_dir = target_dir.join("opencv");
+		if target_module_dir.exists() {
+			for path in files_with_extension(&target_module_dir, "rs")? {
+				let _ = fs::remove_file(path);
+			}
+		}
+	}
+	copy_bindings(out_dir, target_dir)?;
+	Manifest {
+		opencv_version: opencv_version.clone(),
+		crate_version: crate_version(),
+		modules: modules.to_vec(),
+	}
+	.write(&target_dir.join(MANIFEST_FILE))
+}
+
+fn copy_bindings(src_dir: &Path, target_dir: &Path) -> Result<()> {
+	let target_module_dir = target_dir.join("opencv");
+	fs::create_dir_all(&target_module_dir)?;
+	for path in files_with_predicate(src_dir, is_cpp_file)? {
+		let file_name = path.file_name().expect("Can't get file name");
+		fs::copy(&path, target_dir.join(file_name))
+			.map_err(|e| format!("Can't copy bindings file: {}, error: {e}", path.display()))?;
+	}
+	for path in files_with_extension(&src_dir.join("opencv"), "rs")? {
+		let file_name = path.file_name().expect("Can't get file name");
+		fs::copy(&path, target_module_dir.join(file_name))
+			.map_err(|e| format!("Can't copy module file: {}, error: {e}", path.display()))?;
+	}
+	Ok(())
+}
+
+pub fn is_cpp_file(path: &Path) -> bool {
+	path.extension().map_or(false, |ext| {
+		[OsStr::new("cpp"), OsStr::new("hpp")]
+			.iter()
+			.any(|cpp_ext| ext.eq_ignore_ascii_case(cpp_ext))
+	})
+}
+
+fn crate_version() -> String {
+	env::var("CARGO_PKG_VERSION").unwrap_or_else(|_| "unknown_crate_version".to_string())
+}
+
+/// Describes the environment the pregenerated bindings were created in
+///
+/// Stored as a simple `key = value` text file, e.g.:
+/// ```text
+/// opencv_version = 4.10.0
+/// crate_version = 0.93.1
+/// modules = core,imgcodecs,imgproc
+/// ```
+struct Manifest {
+	opencv_version: Version,
+	crate_version: String,
+	modules: Vec<String>,
+}
+
+impl Manifest {
+	fn read(path: &Path) -> Result<Self> {
+		let contents = fs::read_to_string(path).map_err(|e| {
+			format!(
+				"Can't read the pregenerated bindings manifest: {}, error: {e}",
+				path.display()
+			)
+		})?;
+		let values = contents
+			.lines()
+			.map(str::trim)
+			.filter(|line| !line.is_empty() && !line.starts_with('#'))
+			.filter_map(|line| line.split_once('='))
+			.map(|(key, value)| (key.trim(), value.trim()))
+			.collect::<HashMap<_, _>>();
+		let value = |key: &str| {
+			values
+				.get(key)
+				.copied()
+				.ok_or_else(|| format!("Missing {key} in the pregenerated bindings manifest: {}", path.display()))
+		};
+		Ok(Self {
+			opencv_version: Version::parse(value("opencv_version")?)?,
+			crate_version: value("crate_version")?.to_string(),
+			modules: value("modules")?
+				.split(',')
+				.map(str::trim)
+				.filter(|module| !module.is_empty())
+				.map(str::to_string)
+				.collect(),
+		})
+	}
+
+	fn write(&self, path: &Path) -> Result<()> {
+		let contents = format!(
+			"opencv_version = {}\ncrate_version = {}\nmodules = {}\n",
+			self.opencv_version,
+			self.crate_version,
+			self.modules.join(",")
+		);
+		Ok(fs::write(path, contents)?)
+	}
+
+	fn validate(&self, opencv_version: &Version, modules: &[String]) -> Result<()> {
+		if self.opencv_version != *opencv_version {
+			return Err(format!(
+				"Pregenerated bindings are for OpenCV version: {}, but the detected OpenCV version is: {opencv_version}",
+				self.opencv_version
+			)
+			.into());
+		}
+		// the exported FFI function names have the crate version suffix, see `Collector::inject_ffi_exports()`
+		let crate_version = crate_version();
+		if self.crate_version != crate_version {
+			return Err(format!(
+				"Pregenerated bindings are for the opencv crate version: {}, but the current crate version is: {crate_version}",
+				self.crate_version
+			)
+			.into());
+		}
+		let missing_modules = modules
+			.iter()
+			.filter(|module| !self.modules.contains(module))
+			.map(String::as_str)
+			.collect::<Vec<_>>();
+		if !missing_modules.is_empty() {
+			return Err(format!(
+				"Pregenerated bindings are missing the following enabled OpenCV modules: {}, either regenerate the bindings or disable the corresponding crate features",
+				missing_modules.join(", ")
+			)
+			.into());
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,201 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use super::library::Library;
+use super::{Result, AFFECTING_ENV_VARS};
+
+/// Subdirectory of OUT_DIR for the report, the root of OUT_DIR is cleaned up before generating the bindings
+const REPORT_DIR: &str = "probe_report";
+/// Name of the report file, its full path is exposed to the dependent build scripts as
+/// `DEP_OPENCV_<MAJOR>_<MINOR>_PROBE_REPORT`
+const REPORT_FILE: &str = "opencv-probe-report.json";
+
+#[derive(Debug)]
+pub enum ProbeOutcome {
+	Success,
+	Failed(String),
+	Skipped(String),
+}
+
+impl ProbeOutcome {
+	fn status(&self) -> &'static str {
+		match self {
+			Self::Success => "success",
+			Self::Failed(_) => "failed",
+			Self::Skipped(_) => "skipped",
+		}
+	}
+
+	fn message(&self) -> Option<&str> {
+		match self {
+			Self::Success => None,
+			Self::Failed(msg) | Self::Skipped(msg) => Some(msg),
+		}
+	}
+}
+
+/// Machine-readable summary of how the OpenCV library was found, written as JSON to OUT_DIR
+///
+/// Mirrors the `=== ...` lines of the build log, but is meant to be consumed by the dependent build scripts and tooling, e.g.:
+/// ```json
+/// {
+///   "crate_version": "0.93.1",
+///   "env": { "OPENCV_LINK_LIBS": null, ... },
+///   "probes": [
+///     { "name": "pkg_config", "status": "failed", "message": "..." },
+///     { "name": "cmake", "status": "success", "message": null }
+///   ],
+///   "probe": "cmake",
+///   "version": "4.10.0",
+///   "include_paths": ["/usr/include/opencv4"],
+///   "link_paths": ["native=/usr/lib"],
+///   "link_libs": ["opencv_core", "opencv_imgproc"],
+///   "modules": ["core", "imgproc"],
+///   "error": null
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct ProbeReport {
+	probes: Vec<(String, ProbeOutcome)>,
+	version: Option<String>,
+	include_paths: Vec<PathBuf>,
+	link_paths: Vec<String>,
+	link_libs: Vec<String>,
+	modules: Vec<String>,
+	error: Option<String>,
+}
+
+impl ProbeReport {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn add_probe(&mut self, name: &str, outcome: ProbeOutcome) {
+		self.probes.push((name.to_string(), outcome));
+	}
+
+	pub fn set_library(&mut self, library: &Library) {
+		self.version = Some(library.version.to_string());
+		self.include_paths = library.include_paths.clone();
+		self.link_paths = Self::metadata_values(library, "rustc-link-search=");
+		self.link_libs = Self::metadata_values(library, "rustc-link-lib=");
+	}
+
+	pub fn set_modules(&mut self, modules: &[String]) {
+		self.modules = modules.to_vec();
+	}
+
+	pub fn set_error(&mut self, error: &str) {
+		self.error = Some(error.to_string());
+	}
+
+	fn metadata_values(library: &Library, key: &str) -> Vec<String> {
+		library
+			.cargo_metadata
+			.iter()
+			.filter_map(|meta| {
+				meta.strip_prefix("cargo::")
+					.or_else(|| meta.strip_prefix("cargo:"))
+					.and_then(|meta| meta.strip_prefix(key))
+					.map(str::to_string)
+			})
+			.collect()
+	}
+
+	fn to_json(&self) -> String {
+		let mut out = String::with_capacity(4096);
+		out.push_str("{\n");
+		let crate_version = env::var("CARGO_PKG_VERSION").ok();
+		let _ = writeln!(out, "  \"crate_version\": {},", json_opt_string(crate_version.as_deref()));
+		let env = AFFECTING_ENV_VARS
+			.iter()
+			.map(|var| {
+				let value = env::var_os(var).map(|v| v.to_string_lossy().into_owned());
+				format!("\n    {}: {}", json_string(var), json_opt_string(value.as_deref()))
+			})
+			.collect::<Vec<_>>();
+		let _ = writeln!(out, "  \"env\": {{{}\n  }},", env.join(","));
+		let probes = self
+			.probes
+			.iter()
+			.map(|(name, outcome)| {
+				format!(
+					"\n    {{ \"name\": {}, \"status\": {}, \"message\": {} }}",
+					json_string(name),
+					json_string(outcome.status()),
+					json_opt_string(outcome.message())
+				)
+			})
+			.collect::<Vec<_>>();
+		let _ = writeln!(out, "  \"probes\": [{}\n  ],", probes.join(","));
+		let chosen_probe = self
+			.probes
+			.iter()
+			.find(|(_, outcome)| matches!(outcome, ProbeOutcome::Success))
+			.map(|(name, _)| name.as_str());
+		let _ = writeln!(out, "  \"probe\": {},", json_opt_string(chosen_probe));
+		let _ = writeln!(out, "  \"version\": {},", json_opt_string(self.version.as_deref()));
+		let include_paths = self.include_paths.iter().map(|p| p.to_string_lossy()).collect::<Vec<_>>();
+		let _ = writeln!(out, "  \"include_paths\": {},", json_string_array(&include_paths));
+		let _ = writeln!(out, "  \"link_paths\": {},", json_string_array(&self.link_paths));
+		let _ = writeln!(out, "  \"link_libs\": {},", json_string_array(&self.link_libs));
+		let _ = writeln!(out, "  \"modules\": {},", json_string_array(&self.modules));
+		let _ = writeln!(out, "  \"error\": {}", json_opt_string(self.error.as_deref()));
+		out.push_str("}\n");
+		out
+	}
+
+	/// Writes the report to `out_dir` and emits its path along with the probed values as the cargo metadata available to the
+	/// build scripts of the dependent crates as `DEP_OPENCV_<MAJOR>_<MINOR>_*` environment vars
+	pub fn write(&self, out_dir: &Path) -> Result<()> {
+		let dir = out_dir.join(REPORT_DIR);
+		fs::create_dir_all(&dir)?;
+		let path = dir.join(REPORT_FILE);
+		fs::write(&path, self.to_json())
+			.map_err(|e| format!("Can't write OpenCV probe report: {}, error: {e}", path.display()))?;
+		eprintln!("=== Written OpenCV probe report to: {}", path.display());
+		println!("cargo:probe_report={}", path.display()); // replace with cargo:: syntax when MSRV is 1.77
+		if let Some(version) = &self.version {
+			println!("cargo:version={version}"); // replace with cargo:: syntax when MSRV is 1.77
+		}
+		if let Ok(include_paths) = env::join_paths(&self.include_paths).map(|paths| paths.to_string_lossy().into_owned()) {
+			if !include_paths.is_empty() {
+				println!("cargo:include={include_paths}"); // replace with cargo:: syntax when MSRV is 1.77
+			}
+		}
+		if !self.modules.is_empty() {
+			println!("cargo:modules={}", self.modules.join(",")); // replace with cargo:: syntax when MSRV is 1.77
+		}
+		Ok(())
+	}
+}
+
+fn json_string(s: &str) -> String {
+	let mut out = String::with_capacity(s.len() + 2);
+	out.push('"');
+	for c in s.chars() {
+		match c {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			'\t' => out.push_str("\\t"),
+			c if c.is_control() => {
+				let _ = write!(out, "\\u{:04x}", u32::from(c));
+			}
+			c => out.push(c),
+		}
+	}
+	out.push('"');
+	out
+}
+
+fn json_opt_string(s: Option<&str>) -> String {
+	s.map_or_else(|| "null".to_string(), json_string)
+}
+
+fn json_string_array(values: &[impl AsRef<str>]) -> String {
+	let values = values.iter().map(|v| json_string(v.as_ref())).collect::<Vec<_>>();
+	format!("[{}]", values.join(", "))
+}
@@ -0,0 +1,248 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Instant;
+use std::{env, fs};
+
+use shlex::Shlex;
+
+use super::cmake_probe::{LinkLib, LinkSearch};
+use super::library::Linkage;
+use super::{Result, OUT_DIR, TARGET_ENV_MSVC, TARGET_VENDOR_APPLE};
+
+/// OpenCV built from the source checkout specified in the `OPENCV_SOURCE_DIR` environment var
+///
+/// Only the modules enabled by the crate features (and their dependencies) are built, as static libraries with the bundled
+/// 3rd-party dependencies, and installed into OUT_DIR. The build dir is kept between the builds so that cmake only rebuilds
+/// what's changed.
+pub struct VendoredBuild {
+	cmake_bin: PathBuf,
+	source_dir: PathBuf,
+	contrib_source_dir: Option<PathBuf>,
+	build_dir: PathBuf,
+	install_dir: PathBuf,
+}
+
+impl VendoredBuild {
+	pub fn from_env() -> Option<Self> {
+		let source_dir = env::var_os("OPENCV_SOURCE_DIR").filter(|dir| !dir.is_empty())?;
+		Some(Self {
+			cmake_bin: env::var_os("OPENCV_CMAKE_BIN").map_or_else(|| "cmake".into(), PathBuf::from),
+			source_dir: PathBuf::from(source_dir),
+			contrib_source_dir: env::var_os("OPENCV_CONTRIB_SOURCE_DIR")
+				.filter(|dir| !dir.is_empty())
+				.map(PathBuf::from),
+			build_dir: OUT_DIR.join("opencv_build"),
+			install_dir: OUT_DIR.join("opencv_install"),
+		})
+	}
+
+	pub fn cmake_bin(&self) -> &Path {
+		&self.cmake_bin
+	}
+
+	pub fn install_dir(&self) -> &Path {
+		&self.install_dir
+	}
+
+	/// Modules enabled by the crate features that are present in the OpenCV (and contrib) source
+	fn modules(&self) -> Vec<String> {
+		let module_dirs = [Some(self.source_dir.join("modules")), self.contrib_module_dir()];
+		let mut out = env::vars_os()
+			.filter_map(|(k, _)| {
+				k.to_str()
+					.and_then(|s| s.strip_prefix("CARGO_FEATURE_"))
+					.map(str::to_lowercase)
+			})
+			.chain(["core".to_string()])
+			.filter(|module| module_dirs.iter().flatten().any(|dir| dir.join(module).is_dir()))
+			.collect::<HashSet<_>>()
+			.into_iter()
+			.collect::<Vec<_>>();
+		out.sort_unstable();
+		out
+	}
+
+	fn contrib_module_dir(&self) -> Option<PathBuf> {
+		self.contrib_source_dir.as_ref().map(|dir| dir.join("modules"))
+	}
+
+	fn static_crt() -> bool {
+		match env::var("OPENCV_MSVC_CRT").as_deref().map(str::trim) {
+			Ok(crt) if crt.eq_ignore_ascii_case("static") => true,
+			Ok(crt) if crt.eq_ignore_ascii_case("dynamic") => false,
+			_ => env::var("CARGO_CFG_TARGET_FEATURE").map_or(false, |features| features.split(',').any(|f| f == "crt-static")),
+		}
+	}
+
+	/// Compilers and target platform for cmake, by default it builds for the host with the compilers it finds itself
+	///
+	/// The compilers are the same that `cc` uses for the C++ glue (so `CC`, `CXX`, `CFLAGS`, `CXXFLAGS` and their per-target
+	/// variants are respected), when `CMAKE_TOOLCHAIN_FILE` is set it takes precedence over everything else.
+	fn toolchain_args() -> Result<Vec<String>> {
+		if let Some(toolchain_file) = env::var_os("CMAKE_TOOLCHAIN_FILE").filter(|file| !file.is_empty()) {
+			return Ok(vec![format!(
+				"-DCMAKE_TOOLCHAIN_FILE={}",
+				path_to_str(Path::new(&toolchain_file))?
+			)]);
+		}
+		let mut out = vec![];
+		// Visual Studio generators ignore the compiler settings, the platform is selected with `-A` in OPENCV_CMAKE_ARGS
+		if !*TARGET_ENV_MSVC {
+			for (lang, cpp) in [("C", false), ("CXX", true)] {
+				let compiler = cc::Build::new().cpp(cpp).cargo_metadata(false).try_get_compiler()?;
+				out.push(format!("-DCMAKE_{lang}_COMPILER={}", path_to_str(compiler.path())?));
+				let flags = compiler.args().iter().map(|arg| arg.to_string_lossy()).collect::<Vec<_>>();
+				if !flags.is_empty() {
+					out.push(format!("-DCMAKE_{lang}_FLAGS={}", flags.join(" ")));
+				}
+			}
+		}
+		if env::var("TARGET").ok() != env::var("HOST").ok() {
+			let system_name = match env::var("CARGO_CFG_TARGET_OS").as_deref() {
+				Ok("linux") => Some("Linux"),
+				Ok("android") => Some("Android"),
+				Ok("windows") => Some("Windows"),
+				Ok("macos") => Some("Darwin"),
+				Ok("ios") => Some("iOS"),
+				Ok("freebsd") => Some("FreeBSD"),
+				_ => None,
+			};
+			if let Some(system_name) = system_name {
+				out.push(format!("-DCMAKE_SYSTEM_NAME={system_name}"));
+			}
+			if let Ok(arch) = env::var("CARGO_CFG_TARGET_ARCH") {
+				out.push(format!("-DCMAKE_SYSTEM_PROCESSOR={arch}"));
+			}
+		}
+		if *TARGET_VENDOR_APPLE {
+			let arch = match env::var("CARGO_CFG_TARGET_ARCH").as_deref() {
+				Ok("aarch64") => "arm64".to_string(),
+				Ok(arch) => arch.to_string(),
+				Err(_) => String::new(),
+			};
+			if !arch.is_empty() {
+				out.push(format!("-DCMAKE_OSX_ARCHITECTURES={arch}"));
+			}
+		}
+		Ok(out)
+	}
+
+	fn configure_args(&self, modules: &[String]) -> Result<Vec<String>> {
+		let mut out = vec![
+			format!("-DCMAKE_INSTALL_PREFIX={}", path_to_str(&self.install_dir)?),
+			// the debug build is slow, huge and links to the debug CRT on Windows while Rust always uses the release one
+			"-DCMAKE_BUILD_TYPE=Release".to_string(),
+			format!("-DBUILD_LIST={}", modules.join(",")),
+			"-DBUILD_SHARED_LIBS=OFF".to_string(),
+			"-DCMAKE_POSITION_INDEPENDENT_CODE=ON".to_string(),
+			"-DOPENCV_FORCE_3RDPARTY_BUILD=ON".to_string(),
+			// requires downloading the binaries during the build
+			"-DWITH_IPP=OFF".to_string(),
+			"-DBUILD_TESTS=OFF".to_string(),
+			"-DBUILD_PERF_TESTS=OFF".to_string(),
+			"-DBUILD_EXAMPLES=OFF".to_string(),
+			"-DBUILD_opencv_apps=OFF".to_string(),
+			"-DBUILD_DOCS=OFF".to_string(),
+			"-DBUILD_JAVA=OFF".to_string(),
+			"-DBUILD_opencv_python2=OFF".to_string(),
+			"-DBUILD_opencv_python3=OFF".to_string(),
+			"-DBUILD_opencv_js=OFF".to_string(),
+			"-DINSTALL_C_EXAMPLES=OFF".to_string(),
+			"-DINSTALL_PYTHON_EXAMPLES=OFF".to_string(),
+		];
+		if *TARGET_ENV_MSVC {
+			let static_crt = if Self::static_crt() {
+				"ON"
+			} else {
+				"OFF"
+			};
+			out.push(format!("-DBUILD_WITH_STATIC_CRT={static_crt}"));
+		}
+		if let Some(contrib_module_dir) = self.contrib_module_dir() {
+			out.push(format!("-DOPENCV_EXTRA_MODULES_PATH={}", path_to_str(&contrib_module_dir)?));
+		}
+		out.extend(Self::toolchain_args()?);
+		// user-specified arguments go last so that they can override the defaults above
+		if let Ok(extra_args) = env::var("OPENCV_CMAKE_ARGS") {
+			out.extend(Shlex::new(&extra_args));
+		}
+		Ok(out)
+	}
+
+	fn run(&self, cmd: &mut Command) -> Result<()> {
+		eprintln!("=== Running: {cmd:?}");
+		let status = cmd
+			.status()
+			.map_err(|e| format!("Can't run cmake: {}, error: {e}", self.cmake_bin.display()))?;
+		if status.success() {
+			Ok(())
+		} else {
+			Err(format!("cmake failed with: {status}").into())
+		}
+	}
+
+	/// Configures, builds and installs OpenCV into [VendoredBuild::install_dir]
+	pub fn build(&self) -> Result<()> {
+		let modules = self.modules();
+		eprintln!(
+			"=== Building OpenCV from source: {} with modules: {}",
+			self.source_dir.display(),
+			modules.join(", ")
+		);
+		// cargo scans the whole directories for changes
+		println!("cargo:rerun-if-changed={}", self.source_dir.display()); // replace with cargo:: syntax when MSRV is 1.77
+		if let Some(contrib_source_dir) = &self.contrib_source_dir {
+			println!("cargo:rerun-if-changed={}", contrib_source_dir.display()); // replace with cargo:: syntax when MSRV is 1.77
+		}
+		let start = Instant::now();
+		fs::create_dir_all(&self.build_dir)?;
+		let mut configure = Command::new(&self.cmake_bin);
+		configure
+			.arg("-S")
+			.arg(&self.source_dir)
+			.arg("-B")
+			.arg(&self.build_dir)
+			.args(self.configure_args(&modules)?);
+		self.run(&mut configure)?;
+
+		let mut build = Command::new(&self.cmake_bin);
+		build
+			.arg("--build")
+			.arg(&self.build_dir)
+			.args(["--config", "Release", "--target", "install"]);
+		if let Ok(jobs) = env::var("NUM_JOBS") {
+			build.args(["--parallel", &jobs]);
+		}
+		self.run(&mut build)?;
+		eprintln!("=== Total OpenCV build time: {:?}", start.elapsed());
+		Ok(())
+	}
+
+	/// Marks the libraries built by [VendoredBuild::build] as static so that they are linked into the crate
+	///
+	/// On MSVC static and import libraries share the `.lib` extension, so the library is considered vendored when it's found in
+	/// one of the link paths inside [VendoredBuild::install_dir].
+	pub fn mark_static(&self, link_paths: &[LinkSearch], link_libs: &mut [LinkLib]) {
+		let vendored_paths = link_paths
+			.iter()
+			.map(|path| &path.1)
+			.filter(|path| path.starts_with(&self.install_dir))
+			.collect::<Vec<_>>();
+		for lib in link_libs.iter_mut().filter(|lib| lib.0 == Linkage::Default) {
+			let file_name = if *TARGET_ENV_MSVC {
+				format!("{}.lib", lib.1)
+			} else {
+				format!("lib{}.a", lib.1)
+			};
+			if vendored_paths.iter().any(|path| path.join(&file_name).is_file()) {
+				lib.0 = Linkage::Static;
+			}
+		}
+	}
+}
+
+fn path_to_str(path: &Path) -> Result<&str> {
+	path.to_str()
+		.ok_or_else(|| format!("Non-UTF-8 path: {}", path.display()).into())
+}
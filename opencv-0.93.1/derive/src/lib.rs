@@ -0,0 +1,162 @@
+//! Derive macros for the [opencv](https://docs.rs/opencv) crate, use them through the `derive` feature of that crate
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Meta, Result, Type};
+
+/// Implements `opencv::core::DataType` for a struct so that it can be used as a `Mat` element
+///
+/// The struct must be `#[repr(C)]` and all of its fields must have the same type that itself implements `DataType` (e.g.
+/// `u8`, `f32` or `Vec3b`). The depth of the struct is the depth of the field type and the channel count is the sum of the
+/// channel counts of all fields:
+/// ```ignore
+/// #[derive(Clone, Copy, DataType)]
+/// #[repr(C)]
+/// struct Bgr16 {
+///     b: u16,
+///     g: u16,
+///     r: u16,
+/// }
+///
+/// assert_eq!(CV_16UC3, Bgr16::opencv_type());
+/// ```
+///
+/// Generic structs, structs with `packed` or `align` representation and structs with padding are rejected at compile time.
+#[proc_macro_derive(DataType)]
+pub fn derive_data_type(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	data_type(&input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn data_type(input: &DeriveInput) -> Result<TokenStream2> {
+	check_repr(input)?;
+	if !input.generics.params.is_empty() {
+		return Err(Error::new(
+			input.generics.span(),
+			"DataType can't be derived for generic structs",
+		));
+	}
+	let fields = match &input.data {
+		Data::Struct(data) => match &data.fields {
+			Fields::Named(fields) => &fields.named,
+			Fields::Unnamed(fields) => &fields.unnamed,
+			Fields::Unit => return Err(Error::new(input.span(), "DataType can't be derived for unit structs")),
+		},
+		Data::Enum(data) => return Err(Error::new(data.enum_token.span(), "DataType can only be derived for structs")),
+		Data::Union(data) => {
+			return Err(Error::new(
+				data.union_token.span(),
+				"DataType can only be derived for structs",
+			))
+		}
+	};
+	let mut field_types = fields.iter().map(|field| &field.ty);
+	let field_type = field_types
+		.next()
+		.ok_or_else(|| Error::new(input.span(), "DataType can't be derived for structs without fields"))?;
+	if let Type::Array(_) | Type::Slice(_) | Type::Tuple(_) | Type::Reference(_) | Type::Ptr(_) = field_type {
+		return Err(Error::new(
+			field_type.span(),
+			"Field type must be a Mat element type like u8, f32 or Vec3b",
+		));
+	}
+	let field_type_str = field_type.to_token_stream().to_string();
+	if let Some(mismatched) = field_types.find(|typ| typ.to_token_stream().to_string() != field_type_str) {
+		return Err(Error::new(
+			mismatched.span(),
+			format!("All fields must have the same type to derive DataType, expected: {field_type_str}"),
+		));
+	}
+	let field_count = fields.len();
+	let name = &input.ident;
+	Ok(quote! {
+		const _: () = ::core::assert!(
+			::core::mem::size_of::<#name>() == #field_count * ::core::mem::size_of::<#field_type>(),
+			"DataType can't be derived for a struct with padding",
+		);
+
+		unsafe impl ::opencv::core::DataType for #name {
+			#[inline]
+			fn opencv_depth() -> i32 {
+				<#field_type as ::opencv::core::DataType>::opencv_depth()
+			}
+
+			#[inline]
+			fn opencv_channels() -> i32 {
+				#field_count as i32 * <#field_type as ::opencv::core::DataType>::opencv_channels()
+			}
+		}
+	})
+}
+
+/// Checks that the struct is `#[repr(C)]` without any `packed` or `align` modifiers
+fn check_repr(input: &DeriveInput) -> Result<()> {
+	let mut is_c = false;
+	for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+		if let Meta::List(_) = attr.meta {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("C") {
+					is_c = true;
+					Ok(())
+				} else {
+					Err(meta.error("DataType can only be derived for structs with #[repr(C)] without other modifiers"))
+				}
+			})?;
+		}
+	}
+	if is_c {
+		Ok(())
+	} else {
+		Err(Error::new(
+			input.ident.span(),
+			"DataType can only be derived for #[repr(C)] structs",
+		))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use syn::parse_quote;
+
+	use super::data_type;
+
+	#[test]
+	fn accepts_valid_structs() {
+		assert!(data_type(&parse_quote! {
+			#[repr(C)]
+			struct Bgr16 {
+				b: u16,
+				g: u16,
+				r: u16,
+			}
+		})
+		.is_ok());
+		assert!(data_type(&parse_quote! {
+			#[derive(Clone, Copy)]
+			#[repr(C)]
+			struct Pair(Vec3f, Vec3f);
+		})
+		.is_ok());
+	}
+
+	#[test]
+	fn rejects_invalid_structs() {
+		let invalid = [
+			parse_quote! { struct NoRepr { a: u8 } },
+			parse_quote! { #[repr(C, packed)] struct Packed { a: u8 } },
+			parse_quote! { #[repr(C, align(8))] struct Aligned { a: u8 } },
+			parse_quote! { #[repr(transparent)] struct Transparent { a: u8 } },
+			parse_quote! { #[repr(C)] struct Mixed { a: u8, b: u16 } },
+			parse_quote! { #[repr(C)] struct Array { a: [u8; 3] } },
+			parse_quote! { #[repr(C)] struct Empty {} },
+			parse_quote! { #[repr(C)] struct Unit; },
+			parse_quote! { #[repr(C)] struct Generic<T> { a: T } },
+			parse_quote! { #[repr(C)] enum Enum { A } },
+		];
+		for input in &invalid {
+			assert!(data_type(input).is_err(), "{} must be rejected", input.ident);
+		}
+	}
+}
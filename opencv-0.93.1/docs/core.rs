@@ -33682,7 +33682,7 @@ pub mod core {
 		/// serialized.
 		#[inline]
 		fn enqueue_host_callback(&mut self, callback: core::Stream_StreamCallback) -> Result<()> {
-			callback_arg!(callback_trampoline(status: i32, user_data: *mut c_void) -> () => user_data in callbacks => callback(status: i32) -> ());
+			callback_arg!(callback_trampoline(status: i32, user_data: *mut c_void) -> () => user_data in callbacks once => callback(status: i32) -> ());
 			userdata_arg!(user_data in callbacks => callback);
 			return_send!(via ocvrs_return);
 			unsafe { sys::cv_cuda_Stream_enqueueHostCallback_StreamCallback_voidX(self.as_raw_mut_Stream(), callback_trampoline, user_data, ocvrs_return.as_mut_ptr()) };
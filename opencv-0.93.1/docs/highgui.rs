@@ -666,11 +666,12 @@ pub mod highgui {
 		extern_container_arg!(trackbarname);
 		extern_container_arg!(winname);
 		callback_arg!(on_change_trampoline(pos: i32, userdata: *mut c_void) -> () => userdata in callbacks => on_change(pos: i32) -> ());
-		userdata_arg!(userdata in callbacks => on_change);
+		userdata_arg!(userdata in callbacks[crate::callbacks::CallbackKey::Trackbar { window: winname.to_string_lossy().into_owned(), trackbar: trackbarname.to_string_lossy().into_owned() }] => on_change);
 		return_send!(via ocvrs_return);
 		unsafe { sys::cv_createTrackbar_const_StringR_const_StringR_intX_int_TrackbarCallback_voidX(trackbarname.opencv_as_extern(), winname.opencv_as_extern(), value.map_or(::core::ptr::null_mut(), |value| value), count, on_change_trampoline, userdata, ocvrs_return.as_mut_ptr()) };
 		return_receive!(unsafe ocvrs_return => ret);
 		let ret = ret.into_result()?;
+		callbacks.register();
 		Ok(ret)
 	}
 	
@@ -697,6 +698,7 @@ pub mod highgui {
 		unsafe { sys::cv_destroyAllWindows(ocvrs_return.as_mut_ptr()) };
 		return_receive!(unsafe ocvrs_return => ret);
 		let ret = ret.into_result()?;
+		crate::callbacks::release_all_windows();
 		Ok(ret)
 	}
 	
@@ -713,6 +715,7 @@ pub mod highgui {
 		unsafe { sys::cv_destroyWindow_const_StringR(winname.opencv_as_extern(), ocvrs_return.as_mut_ptr()) };
 		return_receive!(unsafe ocvrs_return => ret);
 		let ret = ret.into_result()?;
+		crate::callbacks::release_window(&winname.to_string_lossy());
 		Ok(ret)
 	}
 	
@@ -1447,11 +1450,12 @@ pub mod highgui {
 	pub fn set_mouse_callback(winname: &str, on_mouse: crate::highgui::MouseCallback) -> Result<()> {
 		extern_container_arg!(winname);
 		callback_arg!(on_mouse_trampoline(event: i32, x: i32, y: i32, flags: i32, userdata: *mut c_void) -> () => userdata in callbacks => on_mouse(event: i32, x: i32, y: i32, flags: i32) -> ());
-		userdata_arg!(userdata in callbacks => on_mouse);
+		userdata_arg!(userdata in callbacks[crate::callbacks::CallbackKey::MouseCallback { window: winname.to_string_lossy().into_owned() }] => on_mouse);
 		return_send!(via ocvrs_return);
 		unsafe { sys::cv_setMouseCallback_const_StringR_MouseCallback_voidX(winname.opencv_as_extern(), on_mouse_trampoline, userdata, ocvrs_return.as_mut_ptr()) };
 		return_receive!(unsafe ocvrs_return => ret);
 		let ret = ret.into_result()?;
+		callbacks.register();
 		Ok(ret)
 	}
 	
@@ -1517,11 +1521,12 @@ pub mod highgui {
 	pub fn set_opengl_draw_callback(winname: &str, on_opengl_draw: crate::highgui::OpenGlDrawCallback) -> Result<()> {
 		extern_container_arg!(winname);
 		callback_arg!(on_opengl_draw_trampoline(userdata: *mut c_void) -> () => userdata in callbacks => on_opengl_draw() -> ());
-		userdata_arg!(userdata in callbacks => on_opengl_draw);
+		userdata_arg!(userdata in callbacks[crate::callbacks::CallbackKey::OpenGlDrawCallback { window: winname.to_string_lossy().into_owned() }] => on_opengl_draw);
 		return_send!(via ocvrs_return);
 		unsafe { sys::cv_setOpenGlDrawCallback_const_StringR_OpenGlDrawCallback_voidX(winname.opencv_as_extern(), on_opengl_draw_trampoline, userdata, ocvrs_return.as_mut_ptr()) };
 		return_receive!(unsafe ocvrs_return => ret);
 		let ret = ret.into_result()?;
+		callbacks.register();
 		Ok(ret)
 	}
 	
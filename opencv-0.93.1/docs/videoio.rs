@@ -2517,4 +2517,5 @@ pub mod videoio {
 				.finish()
 		}
 	}
+pub use crate::manual::videoio::*;
 }
@@ -0,0 +1,206 @@
+//! Typed access to the OpenCV build configuration, see [build_info]
+
+use crate::core;
+use crate::Result;
+
+/// Returns the configuration of the OpenCV library the crate is running against
+///
+/// The information is parsed from [core::get_build_information], so it describes the OpenCV that was loaded at runtime, which
+/// can differ from the one the crate was built against when linking dynamically. Use it to check for the optional functionality:
+/// ```no_run
+/// # fn main() -> opencv::Result<()> {
+/// let info = opencv::build_info()?;
+/// if info.has_videoio_backend("GStreamer") {
+///     // use the GStreamer pipeline
+/// }
+/// if info.has_module("dnn") && info.cuda().map_or(false, |cuda| cuda.is_enabled()) {
+///     // try the CUDA backend, `dnn::get_available_backends()` is the definitive check
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn build_info() -> Result<BuildInfo> {
+	Ok(BuildInfo::parse(&core::get_build_information()?))
+}
+
+/// Single `name: value` line of the build information together with the nested lines
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BuildInfoItem {
+	pub name: String,
+	pub value: String,
+	pub children: Vec<BuildInfoItem>,
+}
+
+impl BuildInfoItem {
+	/// Returns `false` if the value indicates that the feature is not available, e.g. `NO` or `-`
+	pub fn is_enabled(&self) -> bool {
+		self.value.split_whitespace().next().map_or(false, |status| {
+			!status.eq_ignore_ascii_case("NO") && !status.eq_ignore_ascii_case("none") && status != "-"
+		})
+	}
+
+	/// Returns the nested item with the `name` (case-insensitive)
+	pub fn child(&self, name: &str) -> Option<&BuildInfoItem> {
+		find_item(&self.children, name)
+	}
+
+	fn value_list(&self) -> Vec<String> {
+		if self.is_enabled() {
+			self.value.split_whitespace().map(str::to_string).collect()
+		} else {
+			vec![]
+		}
+	}
+}
+
+/// OpenCV build configuration parsed from the output of [core::get_build_information], returned by [build_info]
+///
+/// The commonly used values are available as fields, everything else can be looked up in [BuildInfo::items] which mirrors
+/// the nesting of the sections in the build information text.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BuildInfo {
+	/// OpenCV version, e.g. `4.10.0`
+	pub version: String,
+	/// OpenCV modules that were built, from `OpenCV modules: To be built`
+	pub modules: Vec<String>,
+	/// OpenCV modules that the crate has bindings for, this is the same list that's available at compile time via the
+	/// `ocvrs_has_module_*` cfgs
+	pub crate_modules: Vec<&'static str>,
+	/// Video I/O backends with their status, e.g. `FFMPEG: YES`, `GStreamer: NO`
+	pub videoio_backends: Vec<BuildInfoItem>,
+	/// Parallel framework used by OpenCV, e.g. `pthreads` or `TBB (ver 2021.12 interface 12120)`
+	pub parallel_framework: Option<String>,
+	/// CPU instruction sets required by the library, from `CPU/HW features: Baseline`
+	pub cpu_baseline: Vec<String>,
+	/// CPU instruction sets with optimizations selected at runtime, from `CPU/HW features: Dispatched code generation`
+	pub cpu_dispatch: Vec<String>,
+	/// Other 3rd-party libraries with their status, e.g. `Intel IPP`, `Lapack`, `Eigen`, `Protobuf`
+	pub third_party: Vec<BuildInfoItem>,
+	/// All top-level sections of the build information
+	pub items: Vec<BuildInfoItem>,
+}
+
+impl BuildInfo {
+	/// Parses the text returned by [core::get_build_information], lines not following the `name: value` format are ignored
+	pub fn parse(build_information: &str) -> Self {
+		let mut version = String::new();
+		// stack of the items that can still receive children together with the indentation of their names
+		let mut stack: Vec<(usize, BuildInfoItem)> = vec![];
+		let mut items = vec![];
+		// position where the value of the last item starts, the long values are wrapped to continue from that position
+		let mut value_column = usize::MAX;
+		for line in build_information.lines() {
+			let content = line.trim_start();
+			let indent = line.len() - content.len();
+			if content.is_empty() || content.starts_with("---") {
+				continue;
+			}
+			if let Some(header) = content.strip_prefix("General configuration for OpenCV ") {
+				version = header.split_whitespace().next().unwrap_or_default().to_string();
+				continue;
+			}
+			if indent >= value_column {
+				if let Some((_, last)) = stack.last_mut() {
+					last.value.push(' ');
+					last.value.push_str(content.trim_end());
+					continue;
+				}
+			}
+			let (name, value) = if let Some((name, value)) = content.split_once(':') {
+				(name, value)
+			} else {
+				continue;
+			};
+			value_column = if value.trim().is_empty() {
+				usize::MAX
+			} else {
+				indent + name.len() + 1 + (value.len() - value.trim_start().len())
+			};
+			while stack.last().map_or(false, |(last_indent, _)| *last_indent >= indent) {
+				pop_item(&mut stack, &mut items);
+			}
+			stack.push((
+				indent,
+				BuildInfoItem {
+					name: name.trim().to_string(),
+					value: value.trim().to_string(),
+					children: vec![],
+				},
+			));
+		}
+		while !stack.is_empty() {
+			pop_item(&mut stack, &mut items);
+		}
+
+		let section = |name| find_item(&items, name);
+		let child = |section_name, name| section(section_name).and_then(|s| s.child(name));
+		Self {
+			version,
+			modules: child("OpenCV modules", "To be built").map_or_else(Vec::new, BuildInfoItem::value_list),
+			crate_modules: crate_modules(),
+			videoio_backends: section("Video I/O").map_or_else(Vec::new, |s| s.children.clone()),
+			parallel_framework: section("Parallel framework").map(|s| s.value.clone()),
+			cpu_baseline: child("CPU/HW features", "Baseline").map_or_else(Vec::new, BuildInfoItem::value_list),
+			cpu_dispatch: child("CPU/HW features", "Dispatched code generation").map_or_else(Vec::new, BuildInfoItem::value_list),
+			third_party: section("Other third-party libraries").map_or_else(Vec::new, |s| s.children.clone()),
+			items,
+		}
+	}
+
+	/// Returns the top-level section with the `name` (case-insensitive), e.g. `GUI`, `Media I/O` or `OpenCL`
+	pub fn item(&self, name: &str) -> Option<&BuildInfoItem> {
+		find_item(&self.items, name)
+	}
+
+	/// Returns `true` if the OpenCV module was built
+	pub fn has_module(&self, name: &str) -> bool {
+		self.modules.iter().any(|module| module == name)
+	}
+
+	/// Returns the Video I/O backend with the `name` (case-insensitive), e.g. `FFMPEG`, `GStreamer` or `v4l/v4l2`
+	pub fn videoio_backend(&self, name: &str) -> Option<&BuildInfoItem> {
+		find_item(&self.videoio_backends, name)
+	}
+
+	/// Returns `true` if the Video I/O backend with the `name` (case-insensitive) is enabled
+	pub fn has_videoio_backend(&self, name: &str) -> bool {
+		self.videoio_backend(name).map_or(false, BuildInfoItem::is_enabled)
+	}
+
+	/// OpenCL support, the runtime availability can be checked with `core::have_opencl()`
+	pub fn opencl(&self) -> Option<&BuildInfoItem> {
+		self.item("OpenCL")
+	}
+
+	/// CUDA support, present only when OpenCV was built with CUDA
+	pub fn cuda(&self) -> Option<&BuildInfoItem> {
+		self.item("NVIDIA CUDA")
+	}
+
+	/// cuDNN support for the CUDA backend of the `dnn` module, present only when OpenCV was built with CUDA
+	pub fn cudnn(&self) -> Option<&BuildInfoItem> {
+		self.item("cuDNN")
+	}
+}
+
+fn pop_item(stack: &mut Vec<(usize, BuildInfoItem)>, items: &mut Vec<BuildInfoItem>) {
+	if let Some((_, item)) = stack.pop() {
+		if let Some((_, parent)) = stack.last_mut() {
+			parent.children.push(item);
+		} else {
+			items.push(item);
+		}
+	}
+}
+
+fn find_item<'i>(items: &'i [BuildInfoItem], name: &str) -> Option<&'i BuildInfoItem> {
+	items.iter().find(|item| item.name.eq_ignore_ascii_case(name))
+}
+
+fn crate_modules() -> Vec<&'static str> {
+	option_env!("OCVRS_MODULES")
+		.unwrap_or_default()
+		.split(',')
+		.filter(|module| !module.is_empty())
+		.collect()
+}
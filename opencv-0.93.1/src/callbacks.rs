@@ -0,0 +1,230 @@
+//! Lifetime management for the closures passed to OpenCV as callbacks
+//!
+//! OpenCV only receives a raw pointer to the boxed closure, so the closures for callback setters that can be called repeatedly
+//! (like [highgui::set_mouse_callback](crate::highgui::set_mouse_callback) or
+//! [highgui::create_trackbar](crate::highgui::create_trackbar)) are stored in a global registry keyed by [CallbackKey]. The
+//! previous closure is dropped when it's replaced by a new one for the same key, when `None` is passed to the setter or when the
+//! corresponding window is destroyed with [highgui::destroy_window](crate::highgui::destroy_window) or
+//! [highgui::destroy_all_windows](crate::highgui::destroy_all_windows).
+//!
+//! The closures are reference-counted and the running callback holds its own reference, so a closure that replaces or
+//! unregisters itself (e.g. by calling `set_mouse_callback()` for its own window or `destroy_window()`) is only dropped after
+//! it returns.
+
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+
+use once_cell::sync::Lazy;
+
+use crate::sync;
+
+static REGISTRY: Lazy<Mutex<CallbackRegistry>> = Lazy::new(|| Mutex::new(CallbackRegistry::default()));
+
+/// Identifies the closure registered with OpenCV
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CallbackKey {
+	/// [highgui::set_mouse_callback](crate::highgui::set_mouse_callback)
+	MouseCallback { window: String },
+	/// [highgui::set_opengl_draw_callback](crate::highgui::set_opengl_draw_callback)
+	OpenGlDrawCallback { window: String },
+	/// [highgui::create_trackbar](crate::highgui::create_trackbar)
+	Trackbar { window: String, trackbar: String },
+}
+
+impl CallbackKey {
+	/// Name of the window the callback is attached to
+	pub fn window(&self) -> &str {
+		match self {
+			Self::MouseCallback { window } | Self::OpenGlDrawCallback { window } | Self::Trackbar { window, .. } => window,
+		}
+	}
+}
+
+/// Converts the closure into the `userdata` pointer passed to OpenCV, it's released with [drop_userdata] or with
+/// [take_userdata]
+pub(crate) fn into_userdata<F: Send + 'static>(callback: F) -> *mut c_void {
+	Arc::into_raw(Arc::new(Mutex::new(callback))).cast_mut().cast()
+}
+
+/// Returns a new reference to the closure behind the `userdata`, it keeps the closure alive for the duration of the call even
+/// if the closure unregisters itself
+///
+/// # Safety
+/// `userdata` must be produced by [into_userdata] with the same `F` and must not be released yet
+pub(crate) unsafe fn clone_userdata<F>(userdata: *mut c_void) -> Arc<Mutex<F>> {
+	let userdata = userdata.cast_const().cast::<Mutex<F>>();
+	Arc::increment_strong_count(userdata);
+	Arc::from_raw(userdata)
+}
+
+/// Takes the ownership of the closure behind the `userdata`
+///
+/// # Safety
+/// `userdata` must be produced by [into_userdata] with the same `F`, it must not be used after this call
+pub(crate) unsafe fn take_userdata<F>(userdata: *mut c_void) -> Arc<Mutex<F>> {
+	Arc::from_raw(userdata.cast_const().cast::<Mutex<F>>())
+}
+
+/// Releases the reference to the closure held by the `userdata`
+///
+/// # Safety
+/// Same as [take_userdata]
+unsafe fn drop_userdata<F>(userdata: *mut c_void) {
+	drop(take_userdata::<F>(userdata));
+}
+
+/// Calls the closure, the reentrant call (e.g. when the closure itself pumps the GUI events with `wait_key()`) is skipped
+/// and returns the default value because the closure is already borrowed mutably
+pub(crate) fn call<F, R: Default>(callback: &Mutex<F>, f: impl FnOnce(&mut F) -> R) -> R {
+	match callback.try_lock() {
+		Ok(mut callback) => f(&mut callback),
+		Err(TryLockError::Poisoned(e)) => f(&mut e.into_inner()),
+		Err(TryLockError::WouldBlock) => R::default(),
+	}
+}
+
+/// Type-erased owner of a callback, the pointer returned by [Callback::userdata] stays valid until it's dropped and the
+/// running calls of the callback return
+pub(crate) struct Callback {
+	userdata: *mut c_void,
+	drop: unsafe fn(*mut c_void),
+}
+
+// SAFETY: only `Send` values are accepted in `new()`
+unsafe impl Send for Callback {}
+
+impl Callback {
+	pub fn new<T: Send + 'static>(callback: T) -> Self {
+		Self {
+			userdata: into_userdata(callback),
+			drop: drop_userdata::<T>,
+		}
+	}
+
+	/// Pointer to the callback value that is passed to OpenCV as `userdata`
+	#[inline]
+	pub fn userdata(&self) -> *mut c_void {
+		self.userdata
+	}
+}
+
+impl Drop for Callback {
+	fn drop(&mut self) {
+		unsafe { (self.drop)(self.userdata) }
+	}
+}
+
+impl fmt::Debug for Callback {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Callback").field("userdata", &self.userdata).finish()
+	}
+}
+
+/// Storage for the callbacks, the removed callbacks are returned to the caller to be dropped
+#[derive(Debug, Default)]
+pub(crate) struct CallbackRegistry {
+	callbacks: HashMap<CallbackKey, Callback>,
+}
+
+impl CallbackRegistry {
+	/// Stores the `callback` under the `key`, returns the callback previously registered with that key
+	pub fn insert(&mut self, key: CallbackKey, callback: Callback) -> Option<Callback> {
+		self.callbacks.insert(key, callback)
+	}
+
+	/// Removes the callback registered under the `key`
+	pub fn remove(&mut self, key: &CallbackKey) -> Option<Callback> {
+		self.callbacks.remove(key)
+	}
+
+	/// Removes all callbacks attached to the `window`
+	pub fn remove_window(&mut self, window: &str) -> Vec<Callback> {
+		let keys = self
+			.callbacks
+			.keys()
+			.filter(|key| key.window() == window)
+			.cloned()
+			.collect::<Vec<_>>();
+		keys.iter().filter_map(|key| self.callbacks.remove(key)).collect()
+	}
+
+	/// Removes all callbacks
+	pub fn remove_all(&mut self) -> Vec<Callback> {
+		self.callbacks.drain().map(|(_, callback)| callback).collect()
+	}
+
+	#[inline]
+	pub fn contains_key(&self, key: &CallbackKey) -> bool {
+		self.callbacks.contains_key(key)
+	}
+}
+
+fn registry() -> MutexGuard<'static, CallbackRegistry> {
+	sync::lock(&REGISTRY)
+}
+
+/// Returns `true` if there is a closure currently registered with OpenCV under the `key`
+pub fn is_registered(key: &CallbackKey) -> bool {
+	registry().contains_key(key)
+}
+
+/// Drops the closure registered under the `key`, returns `true` if there was one
+///
+/// The safe way to unregister a callback is to pass `None` to the corresponding setter (e.g.
+/// `highgui::set_mouse_callback(winname, None)`), that both resets the callback on the OpenCV side and drops the closure.
+///
+/// # Safety
+/// OpenCV must not call the callback registered under `key` after this function returns.
+pub unsafe fn unregister(key: &CallbackKey) -> bool {
+	// bind to a variable so that the callback is dropped after the registry lock is released
+	let callback = registry().remove(key);
+	callback.is_some()
+}
+
+/// Drops the closures attached to the `window`, called after the window is destroyed
+#[cfg_attr(not(ocvrs_has_module_highgui), allow(dead_code))]
+pub(crate) fn release_window(window: &str) {
+	let callbacks = registry().remove_window(window);
+	drop(callbacks);
+}
+
+/// Drops the closures of all windows, called after all windows are destroyed
+#[cfg_attr(not(ocvrs_has_module_highgui), allow(dead_code))]
+pub(crate) fn release_all_windows() {
+	let callbacks = registry().remove_all();
+	drop(callbacks);
+}
+
+/// Callback that is passed to OpenCV, but stored in the registry only after the successful call
+#[cfg_attr(not(ocvrs_has_module_highgui), allow(dead_code))]
+pub(crate) struct Registration {
+	key: CallbackKey,
+	callback: Option<Callback>,
+}
+
+#[cfg_attr(not(ocvrs_has_module_highgui), allow(dead_code))]
+impl Registration {
+	pub fn new<T: Send + 'static>(key: CallbackKey, callback: Option<T>) -> Self {
+		Self {
+			key,
+			callback: callback.map(Callback::new),
+		}
+	}
+
+	pub fn userdata(&self) -> *mut c_void {
+		self.callback.as_ref().map_or(std::ptr::null_mut(), Callback::userdata)
+	}
+
+	/// Replaces the previously registered callback, `None` callback just drops the previous one
+	pub fn register(self) {
+		let Self { key, callback } = self;
+		let previous = if let Some(callback) = callback {
+			registry().insert(key, callback)
+		} else {
+			registry().remove(&key)
+		};
+		drop(previous);
+	}
+}
@@ -1,13 +1,56 @@
 use std::char::TryFromCharError;
-use std::ffi::NulError;
+use std::ffi::{c_char, CStr, NulError};
 use std::fmt;
 use std::num::TryFromIntError;
 
 use crate::core;
+use crate::manual::sys;
+
+/// Kind of the C++ exception that caused the [Error]
+///
+/// The values are shared with `OcvrsExceptionKind` in `ocvrs_common.hpp`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ExceptionKind {
+	/// The error was created on the Rust side, there was no C++ exception
+	Rust,
+	/// `cv::Exception`, the OpenCV specific details are available via [Error::func], [Error::file], [Error::line] and
+	/// [Error::err]
+	OpenCV,
+	/// `std::bad_alloc`
+	BadAlloc,
+	/// Other `std::exception`
+	Std,
+	/// Exception of an unknown type, i.e. not derived from `std::exception`
+	Unknown,
+}
+
+impl ExceptionKind {
+	#[inline]
+	fn from_i32(kind: i32) -> Self {
+		match kind {
+			1 => Self::OpenCV,
+			2 => Self::BadAlloc,
+			3 => Self::Std,
+			_ => Self::Unknown,
+		}
+	}
+}
+
+/// Details of the C++ exception, boxed to keep the size of [Error] (and therefore [Result]) small
+struct ExceptionDetails {
+	kind: ExceptionKind,
+	/// Name of the exported C++ wrapper function that caught the exception
+	cpp_binding: String,
+	func: Option<String>,
+	file: Option<String>,
+	line: Option<i32>,
+	err: Option<String>,
+}
 
 pub struct Error {
 	pub code: i32,
 	pub message: String,
+	details: Option<Box<ExceptionDetails>>,
 }
 
 impl Error {
@@ -16,6 +59,31 @@ impl Error {
 		Self {
 			code,
 			message: message.into(),
+			details: None,
+		}
+	}
+
+	/// Creates an error from the C++ exception details, used by `ocvrs_create_error()`
+	///
+	/// # Safety
+	/// All non-null string pointers in `exception` must point to valid nul-terminated strings
+	pub(crate) unsafe fn from_exception(exception: &sys::Exception) -> Self {
+		unsafe fn opt_string(s: *const c_char) -> Option<String> {
+			(!s.is_null()).then(|| CStr::from_ptr(s).to_string_lossy().into_owned())
+		}
+
+		let kind = ExceptionKind::from_i32(exception.kind);
+		Self {
+			code: exception.code,
+			message: opt_string(exception.msg).unwrap_or_else(|| "Unable to receive error message".to_string()),
+			details: Some(Box::new(ExceptionDetails {
+				kind,
+				cpp_binding: opt_string(exception.binding).unwrap_or_default(),
+				func: opt_string(exception.func),
+				file: opt_string(exception.file),
+				line: (kind == ExceptionKind::OpenCV).then_some(exception.line),
+				err: opt_string(exception.err),
+			})),
 		}
 	}
 
@@ -24,6 +92,46 @@ impl Error {
 		error_code_as_enum(self.code)
 	}
 
+	/// Kind of the C++ exception that caused this error, [ExceptionKind::Rust] if the error was created on the Rust side
+	#[inline]
+	pub fn exception_kind(&self) -> ExceptionKind {
+		self.details.as_ref().map_or(ExceptionKind::Rust, |d| d.kind)
+	}
+
+	/// Symbol name of the exported C++ wrapper function that raised the error, e.g.
+	/// `cv_imgproc_cvtColor_const__InputArrayR_const__OutputArrayR_int`
+	///
+	/// This is the C++ side of the binding, not the Rust function path. The Rust binding calls the `extern "C"` function with
+	/// the same name, so it can be found by searching the generated sources for this symbol.
+	#[inline]
+	pub fn cpp_binding(&self) -> Option<&str> {
+		self.details.as_ref().map(|d| d.cpp_binding.as_str())
+	}
+
+	/// Name of the OpenCV function where the error occurred, `cv::Exception::func`
+	#[inline]
+	pub fn func(&self) -> Option<&str> {
+		self.details.as_ref().and_then(|d| d.func.as_deref())
+	}
+
+	/// Source file name where the error occurred, `cv::Exception::file`
+	#[inline]
+	pub fn file(&self) -> Option<&str> {
+		self.details.as_ref().and_then(|d| d.file.as_deref())
+	}
+
+	/// Line number in the source file where the error occurred, `cv::Exception::line`
+	#[inline]
+	pub fn line(&self) -> Option<i32> {
+		self.details.as_ref().and_then(|d| d.line)
+	}
+
+	/// Error description without the location information, `cv::Exception::err`
+	#[inline]
+	pub fn err(&self) -> Option<&str> {
+		self.details.as_ref().and_then(|d| d.err.as_deref())
+	}
+
 	fn format_code(&self) -> String {
 		if let Some(code) = self.code_as_enum() {
 			format!("{code:?}, {}", self.code)
@@ -36,16 +144,34 @@ impl Error {
 impl fmt::Display for Error {
 	#[inline]
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "{} (code: {})", self.message, self.format_code())
+		write!(f, "{} (code: {})", self.message, self.format_code())?;
+		if let Some(cpp_binding) = self.cpp_binding() {
+			write!(f, " in {cpp_binding}")?;
+		}
+		Ok(())
 	}
 }
 
 impl fmt::Debug for Error {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		f.debug_struct("Error")
-			.field("code", &self.format_code())
-			.field("message", &self.message)
-			.finish()
+		let mut out = f.debug_struct("Error");
+		out.field("code", &self.format_code()).field("message", &self.message);
+		if let Some(details) = &self.details {
+			out.field("kind", &details.kind).field("cpp_binding", &details.cpp_binding);
+			if let Some(func) = &details.func {
+				out.field("func", func);
+			}
+			if let Some(file) = &details.file {
+				out.field("file", file);
+			}
+			if let Some(line) = details.line {
+				out.field("line", &line);
+			}
+			if let Some(err) = &details.err {
+				out.field("err", err);
+			}
+		}
+		out.finish()
 	}
 }
 
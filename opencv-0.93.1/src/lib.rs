@@ -1,5 +1,6 @@
 #![allow(broken_intra_doc_links)]
 
+pub use build_info::{build_info, BuildInfo, BuildInfoItem};
 pub use error::{Error, Result};
 
 pub use crate::opencv::hub::*;
@@ -7,9 +8,11 @@ pub use crate::opencv::hub::*;
 #[macro_use]
 mod templ;
 
+mod build_info;
 pub mod error;
 mod manual;
 mod opencv;
+mod sync;
 pub mod traits;
 
 pub mod prelude {
@@ -48,6 +51,7 @@ pub mod mod_prelude {
 }
 
 pub mod boxed_ref;
+pub mod callbacks;
 mod cond_macros;
 #[cfg(test)]
 mod test;
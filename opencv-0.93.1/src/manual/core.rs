@@ -32,6 +32,8 @@ mod point3;
 pub(crate) mod ptr;
 mod rect;
 mod scalar;
+#[cfg(feature = "serde")]
+mod serde;
 mod size;
 mod sized;
 mod tuple;
@@ -1,3 +1,6 @@
+#[cfg(feature = "derive")]
+pub use opencv_derive::DataType;
+
 use crate::core;
 use crate::core::{Point3_, Point_, Rect_, Size_, VecN};
 
@@ -19,6 +22,9 @@ pub const fn CV_MAKETYPE(depth: i32, cn: i32) -> i32 {
 /// Types implementing this trait must adhere to the memory layout declared by the values returned
 /// by `opencv_depth()` and `opencv_channels()` functions. In most cases that means that the type
 /// must also be `#[repr(C)]`.
+///
+/// With `derive` feature enabled you can use `#[derive(DataType)]` to implement it safely for your own `#[repr(C)]` structs
+/// whose fields all have the same element type.
 pub unsafe trait DataType: Copy {
 	/// The shape of bytes occupied by the single layer/channel of the element. E.g. for an 8-bit BGR
 	/// image it's `CV_8U` because a single channel for a pixel is unsigned 8 bits. You should use one
@@ -62,6 +68,8 @@ data_type!(i32, core::CV_32S, 1);
 // float
 data_type!(f32, core::CV_32F, 1);
 data_type!(f64, core::CV_64F, 1);
+#[cfg(all(feature = "half", not(ocvrs_opencv_branch_32)))]
+data_type!(half::f16, core::CV_16F, 1);
 
 #[cfg(feature = "rgb")]
 data_type!(rgb::RGB8, core::CV_8U, 3);
@@ -80,6 +88,28 @@ data_type!(rgb::alt::BGRA8, core::CV_8U, 4);
 #[cfg(feature = "rgb")]
 data_type!(rgb::alt::ABGR8, core::CV_8U, 4);
 
+macro_rules! data_type_image {
+	($rust_type: ident, $channels: expr) => {
+		#[cfg(feature = "image")]
+		unsafe impl<T: DataType> DataType for image::$rust_type<T> {
+			#[inline]
+			fn opencv_depth() -> i32 {
+				T::opencv_depth()
+			}
+
+			#[inline]
+			fn opencv_channels() -> i32 {
+				$channels
+			}
+		}
+	};
+}
+
+data_type_image!(Luma, 1);
+data_type_image!(LumaA, 2);
+data_type_image!(Rgb, 3);
+data_type_image!(Rgba, 4);
+
 unsafe impl<T: DataType, const N: usize> DataType for VecN<T, N> {
 	#[inline]
 	fn opencv_depth() -> i32 {
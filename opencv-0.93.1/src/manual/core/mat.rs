@@ -1,18 +1,49 @@
 use std::convert::TryInto;
 use std::ffi::c_void;
+use std::io::Write;
+use std::iter::Flatten;
 use std::marker::PhantomData;
 use std::ops::Deref;
+use std::path::Path;
 use std::{fmt, mem, ptr, slice};
 
+pub use allocator::{MatAllocator, TrackingMatAllocator};
 pub use mat_::*;
+pub use pool::{MatPool, MatPoolStats, PooledMat};
+pub use rows::{MatRowsIter, MatRowsIterMut};
+pub use shared_mat::SharedMat;
+#[cfg(target_os = "linux")]
+pub use shared_memory::{SharedMemoryMat, SharedMemoryMatReader};
+pub use slicing::*;
+pub use typed_mat::TypedMat;
 
 use crate::boxed_ref::{BoxedRef, BoxedRefMut};
 use crate::core::{MatConstIterator, MatExpr, MatSize, Point, Rect, Scalar, Size, UMat};
 use crate::manual::core::DataType;
 use crate::prelude::*;
 use crate::{core, input_output_array, input_output_array_vector, Error, Result};
-
+use slicing::{bounds_to_ranges, split_ranges};
+
+mod allocator;
+#[cfg(all(feature = "half", not(ocvrs_opencv_branch_32)))]
+mod half;
+#[cfg(feature = "image")]
+mod image;
+#[cfg(unix)]
+mod mapped;
 mod mat_;
+#[cfg(feature = "ndarray")]
+mod ndarray;
+mod npy;
+#[cfg(feature = "rayon")]
+mod par_iter;
+mod pool;
+mod rows;
+mod shared_mat;
+#[cfg(target_os = "linux")]
+mod shared_memory;
+mod slicing;
+mod typed_mat;
 
 #[inline(always)]
 /// We rely on OpenCV to make sure that the pointer is correctly aligned
@@ -332,6 +363,81 @@ impl Mat {
 			Err(Error::new(core::StsBadArg, "ROIs must not intersect"))
 		}
 	}
+
+	/// Returns mutable ROIs into a single `Mat` as long as none of them intersect
+	///
+	/// The returned ROIs are `Send` so they can be processed in parallel.
+	pub fn roi_n_mut<'m, MAT: MatTrait>(m: &'m mut MAT, rois: &[Rect]) -> Result<Vec<BoxedRefMut<'m, Mat>>> {
+		for (i, roi1) in rois.iter().enumerate() {
+			if let Some(roi2) = rois[i + 1..].iter().find(|roi2| !(*roi1 & **roi2).empty()) {
+				return Err(Error::new(
+					core::StsBadArg,
+					format!("ROIs must not intersect, but {roi1:?} intersects {roi2:?}"),
+				));
+			}
+		}
+		// safe because we made sure that the interest areas do not intersect
+		unsafe { rois_mut_unchecked(m, rois.iter().copied()) }
+	}
+
+	/// Splits the `Mat` into `n` mutable horizontal bands of (almost) equal height
+	///
+	/// If `n` is larger than the row count of the `Mat` then each band contains a single row. The returned bands are `Send` so
+	/// they can be processed in parallel.
+	pub fn split_rows_mut<MAT: MatTrait>(m: &mut MAT, n: usize) -> Result<Vec<BoxedRefMut<Mat>>> {
+		if n == 0 {
+			return Err(Error::new(core::StsBadArg, "Number of bands must be positive"));
+		}
+		let size = m.size()?;
+		// safe because Mat::size() can't be negative
+		let n = n.min(size.height as usize);
+		let rois = (0..n).map(|i| {
+			// safe because `n` is not larger than the row count which fits `i32`
+			let (i, n) = (i as i32, n as i32);
+			let top = i * size.height / n;
+			let bottom = (i + 1) * size.height / n;
+			Rect::new(0, top, size.width, bottom - top)
+		});
+		// safe because the bands do not intersect by construction
+		unsafe { rois_mut_unchecked(m, rois) }
+	}
+
+	/// Splits the `Mat` into mutable tiles of `tile_size` going row by row
+	///
+	/// Tiles on the right and bottom edges are smaller if the `Mat` size is not a multiple of `tile_size`. The returned tiles
+	/// are `Send` so they can be processed in parallel.
+	pub fn tiles_mut<MAT: MatTrait>(m: &mut MAT, tile_size: Size) -> Result<Vec<BoxedRefMut<Mat>>> {
+		if tile_size.width <= 0 || tile_size.height <= 0 {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!("Tile size must be positive, but it is: {tile_size:?}"),
+			));
+		}
+		let size = m.size()?;
+		let rois = (0..size.height).step_by(tile_size.height as usize).flat_map(|y| {
+			(0..size.width).step_by(tile_size.width as usize).map(move |x| {
+				Rect::new(
+					x,
+					y,
+					tile_size.width.min(size.width - x),
+					tile_size.height.min(size.height - y),
+				)
+			})
+		});
+		// safe because the tiles do not intersect by construction
+		unsafe { rois_mut_unchecked(m, rois) }
+	}
+}
+
+/// # Safety
+/// Caller must ensure that the passed ROIs do not intersect
+unsafe fn rois_mut_unchecked<MAT: MatTrait>(m: &mut MAT, rois: impl Iterator<Item = Rect>) -> Result<Vec<BoxedRefMut<Mat>>> {
+	rois
+		.map(|roi| {
+			let m = (m as *mut MAT).as_mut().expect("Can't fail");
+			Mat::roi_mut(m, roi)
+		})
+		.collect()
 }
 
 pub struct MatIter<'m, T> {
@@ -578,6 +684,32 @@ pub trait MatTraitConstManual: MatTraitConst {
 		})
 	}
 
+	/// Returns an `ndarray` view over the `Mat` data
+	///
+	/// `T` can be either the element type of the `Mat` (e.g. `Vec3b` for `CV_8UC3`) or a single channel of it (e.g. `u8`), in
+	/// the latter case multichannel `Mat` gets an additional trailing axis for channels. `Mat` steps are respected so the view
+	/// works for ROIs and other non-continuous `Mat`s.
+	#[cfg(feature = "ndarray")]
+	#[inline]
+	fn as_array_view<T: DataType>(&self) -> Result<::ndarray::ArrayViewD<'_, T>> {
+		self::ndarray::array_view(self)
+	}
+
+	/// Converts a `CV_16F` `Mat` into a new `CV_32F` one with the same size and channel count
+	#[cfg(all(feature = "half", not(ocvrs_opencv_branch_32)))]
+	#[inline]
+	fn f16_to_f32(&self) -> Result<Mat> {
+		self::half::convert_float_depth(self, core::CV_16F, core::CV_32F)
+	}
+
+	/// Converts a `CV_32F` `Mat` into a new `CV_16F` one with the same size and channel count, values that are out of `f16`
+	/// range become infinities
+	#[cfg(all(feature = "half", not(ocvrs_opencv_branch_32)))]
+	#[inline]
+	fn f32_to_f16(&self) -> Result<Mat> {
+		self::half::convert_float_depth(self, core::CV_32F, core::CV_16F)
+	}
+
 	fn to_vec_2d<T: DataType>(&self) -> Result<Vec<Vec<T>>> {
 		match_format::<T>(self.typ()).and_then(|_| {
 			let size = match *self.mat_size() {
@@ -619,6 +751,54 @@ pub trait MatTraitConstManual: MatTraitConst {
 		})
 	}
 
+	/// Returns a view into the part of the `Mat` specified by Rust ranges, one per dimension
+	///
+	/// Dimensions that are not covered by the passed ranges are taken in full. Works with `Mat`s of any dimensionality:
+	/// ```no_run
+	/// # use opencv::core::{Mat, Vec3b};
+	/// # use opencv::prelude::*;
+	/// # fn main() -> opencv::Result<()> {
+	/// let m = Mat::new_nd_with_default(&[4, 50, 50], Vec3b::opencv_type(), 0.into())?;
+	/// let part = m.slice((.., 10..20, 5..=9))?;
+	/// assert_eq!(&[4, 10, 5], &*part.mat_size());
+	/// # Ok(())
+	/// # }
+	/// ```
+	#[inline]
+	fn slice(&self, ranges: impl MatSliceRanges) -> Result<BoxedRef<Mat>> {
+		let ranges = bounds_to_ranges(&ranges.to_bounds(), &self.mat_size())?;
+		self.ranges(&ranges)
+	}
+
+	/// Returns an iterator over `Mat` rows as slices
+	///
+	/// For a multidimensional `Mat` a row is a run of elements along its last dimension. `Mat` steps are respected so this
+	/// works for ROIs and other non-continuous `Mat`s. Unlike [MatTraitConstManual::iter] it doesn't cross the FFI boundary for
+	/// every element so it's much faster.
+	#[inline]
+	fn rows_iter<T: DataType>(&self) -> Result<MatRowsIter<T>> {
+		MatRowsIter::new(self)
+	}
+
+	/// Returns an iterator over `Mat` elements that walks the rows in Rust, see [MatTraitConstManual::rows_iter]
+	///
+	/// Unlike [MatTraitConstManual::iter] it doesn't return the element positions, use `rows_iter()` with `enumerate()` if
+	/// you need them.
+	#[inline]
+	fn iter_fast<T: DataType>(&self) -> Result<Flatten<MatRowsIter<T>>> {
+		self.rows_iter().map(Iterator::flatten)
+	}
+
+	/// Returns a `rayon` parallel iterator over `Mat` rows
+	///
+	/// For a multidimensional `Mat` a row is a run of elements along its last dimension. `Mat` steps are respected so this
+	/// works for ROIs and other non-continuous `Mat`s.
+	#[cfg(feature = "rayon")]
+	#[inline]
+	fn par_rows<T: DataType + Sync>(&self) -> Result<::rayon::vec::IntoIter<&[T]>> {
+		par_iter::par_rows(self)
+	}
+
 	/// Returns an iterator over `Mat` elements and their positions
 	#[inline]
 	fn iter<T: DataType>(&self) -> Result<MatIter<T>>
@@ -642,6 +822,19 @@ pub trait MatTraitConstManual: MatTraitConst {
 	{
 		self.try_into()
 	}
+
+	/// Writes the continuous `Mat` in the NumPy `.npy` format, the channels of a multichannel `Mat` are stored as the trailing
+	/// axis
+	#[inline]
+	fn write_npy_to(&self, writer: impl Write) -> Result<()> {
+		npy::write(self, writer)
+	}
+
+	/// Writes the continuous `Mat` to the NumPy `.npy` file, see [MatTraitConstManual::write_npy_to]
+	#[inline]
+	fn write_npy(&self, path: impl AsRef<Path>) -> Result<()> {
+		npy::write_file(self, path.as_ref())
+	}
 }
 
 pub trait MatTraitManual: MatTraitConstManual + MatTrait {
@@ -741,6 +934,65 @@ pub trait MatTraitManual: MatTraitConstManual + MatTrait {
 		})
 	}
 
+	/// Returns a mutable view into the part of the `Mat` specified by Rust ranges, see [MatTraitConstManual::slice]
+	///
+	/// The returned view borrows the `Mat` mutably so only one such view can exist at a time, use [MatTraitManual::split_at_mut]
+	/// to get several mutable views that are guaranteed not to overlap.
+	#[inline]
+	fn slice_mut(&mut self, ranges: impl MatSliceRanges) -> Result<BoxedRefMut<Mat>> {
+		let ranges = bounds_to_ranges(&ranges.to_bounds(), &self.mat_size())?;
+		self.ranges_mut(&ranges)
+	}
+
+	/// Splits the `Mat` into 2 non-overlapping mutable views along dimension `dim`, first view contains indices `0..mid` and
+	/// the second one contains indices `mid..` of that dimension
+	#[inline]
+	fn split_at_mut(&mut self, dim: i32, mid: i32) -> Result<(BoxedRefMut<Mat>, BoxedRefMut<Mat>)> {
+		let (first, second) = split_ranges(dim, mid, &self.mat_size())?;
+		// safe because the ranges do not intersect
+		let m2 = unsafe { (self as *mut Self).as_mut().expect("Can't fail") };
+		Ok((self.ranges_mut(&first)?, m2.ranges_mut(&second)?))
+	}
+
+	/// Returns a mutable `ndarray` view over the `Mat` data, see [MatTraitConstManual::as_array_view]
+	#[cfg(feature = "ndarray")]
+	#[inline]
+	fn as_array_view_mut<T: DataType>(&mut self) -> Result<::ndarray::ArrayViewMutD<'_, T>> {
+		self::ndarray::array_view_mut(self)
+	}
+
+	/// Returns a mutable iterator over `Mat` rows as slices, see [MatTraitConstManual::rows_iter]
+	#[inline]
+	fn rows_iter_mut<T: DataType>(&mut self) -> Result<MatRowsIterMut<T>> {
+		MatRowsIterMut::new(self)
+	}
+
+	/// Returns a mutable `rayon` parallel iterator over `Mat` rows, see [MatTraitConstManual::par_rows]
+	#[cfg(feature = "rayon")]
+	#[inline]
+	fn par_rows_mut<T: DataType + Send>(&mut self) -> Result<::rayon::vec::IntoIter<&mut [T]>> {
+		par_iter::par_rows_mut(self)
+	}
+
+	/// Returns a mutable `rayon` parallel iterator over `Mat` elements
+	///
+	/// Useful for running a per-pixel kernel on all cores:
+	/// ```no_run
+	/// # use opencv::core::{Mat, Vec3b};
+	/// # use opencv::prelude::*;
+	/// # use rayon::iter::ParallelIterator;
+	/// # fn main() -> opencv::Result<()> {
+	/// let mut m = Mat::new_rows_cols_with_default(480, 640, Vec3b::opencv_type(), 0.into())?;
+	/// m.par_pixels_mut::<Vec3b>()?.for_each(|px| px[1] = 255);
+	/// # Ok(())
+	/// # }
+	/// ```
+	#[cfg(feature = "rayon")]
+	#[inline]
+	fn par_pixels_mut<T: DataType + Send>(&mut self) -> Result<::rayon::iter::Flatten<::rayon::vec::IntoIter<&mut [T]>>> {
+		par_iter::par_pixels_mut(self)
+	}
+
 	/// Returns a mutable iterator over `Mat` elements and their positions
 	#[inline]
 	fn iter_mut<T: DataType>(&mut self) -> Result<MatIterMut<T>>
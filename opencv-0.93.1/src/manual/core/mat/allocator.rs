@@ -0,0 +1,195 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::core::Mat;
+use crate::platform_types::size_t;
+use crate::{sync, sys, Result};
+
+/// Alignment of the `Mat` data, same as `CV_MALLOC_ALIGN` used by the default OpenCV allocator
+const MAT_DATA_ALIGN: usize = 64;
+
+type AllocateFn = unsafe extern "C" fn(ctx: *mut c_void, size: size_t) -> *mut c_void;
+type DeallocateFn = unsafe extern "C" fn(ctx: *mut c_void, data: *mut c_void, size: size_t);
+type MapFn = unsafe extern "C" fn(ctx: *mut c_void, data: *mut c_void, size: size_t, access_flags: i32);
+type UnmapFn = unsafe extern "C" fn(ctx: *mut c_void, data: *mut c_void, size: size_t);
+
+extern "C" {
+	fn cv_MatAllocator_new(
+		ctx: *mut c_void,
+		allocate: AllocateFn,
+		deallocate: DeallocateFn,
+		map: MapFn,
+		unmap: UnmapFn,
+	) -> *mut c_void;
+	fn cv_Mat_setDefaultAllocator_MatAllocatorX(allocator: *mut c_void, ocvrs_return: *mut sys::ResultVoid);
+}
+
+/// Allocator for the `Mat` data, adapted into a `cv::MatAllocator` on the C++ side
+///
+/// OpenCV requests the memory for the whole `Mat` data buffer, the `Mat` header and the reference counter are still allocated
+/// by OpenCV itself. See [Mat::set_default_allocator] for installing the allocator and [TrackingMatAllocator] for an example
+/// implementation.
+///
+/// # Safety
+/// The implementation must follow the same contract as [GlobalAlloc]: `allocate` must return either a null pointer (which is
+/// reported as an out-of-memory error) or a pointer to the block of memory that fits the `layout` and stays valid until it's
+/// passed to `deallocate` with the same `layout`.
+pub unsafe trait MatAllocator: Send + Sync {
+	/// Allocates the memory for the `Mat` data
+	///
+	/// # Safety
+	/// Same as [GlobalAlloc::alloc], `layout` always has a non-zero size
+	unsafe fn allocate(&self, layout: Layout) -> *mut u8;
+
+	/// Frees the memory previously returned by [MatAllocator::allocate]
+	///
+	/// # Safety
+	/// Same as [GlobalAlloc::dealloc]
+	unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout);
+
+	/// Called when the `Mat` data is mapped for access by a `UMat` (`cv::Mat::getUMat()`), `access_flags` is the
+	/// combination of `core::ACCESS_*` flags
+	fn map(&self, _data: *mut u8, _size: usize, _access_flags: i32) {}
+
+	/// Called when the last `Mat` referencing the data releases it, right before the data is freed with
+	/// [MatAllocator::deallocate] (the freeing is postponed if the data is still used by a `UMat`)
+	fn unmap(&self, _data: *mut u8, _size: usize) {}
+}
+
+#[inline]
+fn data_layout(size: size_t) -> Option<Layout> {
+	Layout::from_size_align(size.max(1), MAT_DATA_ALIGN).ok()
+}
+
+#[inline]
+unsafe fn allocator_from_ctx(ctx: *mut c_void) -> &'static dyn MatAllocator {
+	*ctx.cast::<&'static dyn MatAllocator>()
+}
+
+unsafe extern "C" fn allocate_trampoline(ctx: *mut c_void, size: size_t) -> *mut c_void {
+	data_layout(size).map_or(std::ptr::null_mut(), |layout| allocator_from_ctx(ctx).allocate(layout).cast())
+}
+
+unsafe extern "C" fn deallocate_trampoline(ctx: *mut c_void, data: *mut c_void, size: size_t) {
+	if let Some(layout) = data_layout(size) {
+		allocator_from_ctx(ctx).deallocate(data.cast(), layout);
+	}
+}
+
+unsafe extern "C" fn map_trampoline(ctx: *mut c_void, data: *mut c_void, size: size_t, access_flags: i32) {
+	allocator_from_ctx(ctx).map(data.cast(), size, access_flags)
+}
+
+unsafe extern "C" fn unmap_trampoline(ctx: *mut c_void, data: *mut c_void, size: size_t) {
+	allocator_from_ctx(ctx).unmap(data.cast(), size)
+}
+
+/// C++ adapters created for the allocators, (allocator, `cv::MatAllocator` pointer)
+///
+/// The adapters are never freed because the `Mat`s allocated by them can outlive the change of the default allocator. The
+/// allocators are compared by the full fat pointer, distinct zero-sized allocators can share the data address and only differ in
+/// the vtable. The same allocator can have its vtable duplicated across codegen units, this only results in an extra adapter.
+static ADAPTERS: Mutex<Vec<(&'static dyn MatAllocator, usize)>> = Mutex::new(Vec::new());
+
+fn adapter(allocator: &'static dyn MatAllocator) -> *mut c_void {
+	let mut adapters = sync::lock(&ADAPTERS);
+	if let Some(&(_, adapter)) = adapters
+		.iter()
+		.find(|(adapter_allocator, _)| std::ptr::eq(*adapter_allocator, allocator))
+	{
+		return adapter as *mut c_void;
+	}
+	let ctx = Box::into_raw(Box::new(allocator)).cast::<c_void>();
+	let adapter = unsafe {
+		cv_MatAllocator_new(
+			ctx,
+			allocate_trampoline,
+			deallocate_trampoline,
+			map_trampoline,
+			unmap_trampoline,
+		)
+	};
+	adapters.push((allocator, adapter as usize));
+	adapter
+}
+
+impl Mat {
+	/// Sets the allocator used for the data of all `Mat`s created afterward, `None` restores the default OpenCV allocator
+	///
+	/// The `Mat`s allocated before the change keep using the allocator they were created with.
+	pub fn set_default_allocator(allocator: Option<&'static dyn MatAllocator>) -> Result<()> {
+		let allocator = allocator.map_or(std::ptr::null_mut(), adapter);
+		return_send!(via ocvrs_return);
+		unsafe { cv_Mat_setDefaultAllocator_MatAllocatorX(allocator, ocvrs_return.as_mut_ptr()) };
+		return_receive!(unsafe ocvrs_return => ret);
+		ret.into_result()
+	}
+}
+
+/// [MatAllocator] that allocates from the system allocator and keeps track of the memory used by the `Mat` data
+///
+/// ```no_run
+/// # use opencv::core::{Mat, TrackingMatAllocator};
+/// # fn main() -> opencv::Result<()> {
+/// static ALLOCATOR: TrackingMatAllocator = TrackingMatAllocator::new();
+///
+/// Mat::set_default_allocator(Some(&ALLOCATOR))?;
+/// // ...
+/// println!("Memory used by Mat data: {} bytes", ALLOCATOR.bytes_in_use());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct TrackingMatAllocator {
+	bytes_in_use: AtomicUsize,
+	peak_bytes: AtomicUsize,
+	allocations: AtomicUsize,
+}
+
+impl TrackingMatAllocator {
+	pub const fn new() -> Self {
+		Self {
+			bytes_in_use: AtomicUsize::new(0),
+			peak_bytes: AtomicUsize::new(0),
+			allocations: AtomicUsize::new(0),
+		}
+	}
+
+	/// Number of bytes currently allocated for the `Mat` data
+	#[inline]
+	pub fn bytes_in_use(&self) -> usize {
+		self.bytes_in_use.load(Ordering::Relaxed)
+	}
+
+	/// Maximum value of [TrackingMatAllocator::bytes_in_use] since the creation
+	#[inline]
+	pub fn peak_bytes(&self) -> usize {
+		self.peak_bytes.load(Ordering::Relaxed)
+	}
+
+	/// Number of the currently live allocations
+	#[inline]
+	pub fn allocations(&self) -> usize {
+		self.allocations.load(Ordering::Relaxed)
+	}
+}
+
+unsafe impl MatAllocator for TrackingMatAllocator {
+	unsafe fn allocate(&self, layout: Layout) -> *mut u8 {
+		let out = System.alloc(layout);
+		if !out.is_null() {
+			let in_use = self.bytes_in_use.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+			self.peak_bytes.fetch_max(in_use, Ordering::Relaxed);
+			self.allocations.fetch_add(1, Ordering::Relaxed);
+		}
+		out
+	}
+
+	unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout);
+		self.bytes_in_use.fetch_sub(layout.size(), Ordering::Relaxed);
+		self.allocations.fetch_sub(1, Ordering::Relaxed);
+	}
+}
@@ -0,0 +1,19 @@
+use crate::core::{Mat, MatTraitConst, CV_MAKETYPE};
+use crate::{core, Error, Result};
+
+/// Converts the floating point `Mat` from `from_depth` into a new `Mat` of `to_depth` keeping the channel count
+pub(crate) fn convert_float_depth(mat: &(impl MatTraitConst + ?Sized), from_depth: i32, to_depth: i32) -> Result<Mat> {
+	if mat.depth() != from_depth {
+		return Err(Error::new(
+			core::StsUnmatchedFormats,
+			format!(
+				"Mat depth: {} must be: {} for this conversion",
+				core::depth_to_string(mat.depth())?,
+				core::depth_to_string(from_depth)?
+			),
+		));
+	}
+	let mut out = Mat::default();
+	mat.convert_to(&mut out, CV_MAKETYPE(to_depth, mat.channels()), 1., 0.)?;
+	Ok(out)
+}
@@ -0,0 +1,214 @@
+use std::ffi::c_void;
+use std::ops::{Deref, DerefMut};
+
+use image::{DynamicImage, ImageBuffer, Pixel};
+
+use crate::boxed_ref::{BoxedRef, BoxedRefMut};
+use crate::core::{Mat, MatTraitConst, MatTraitConstManual, MatTraitManual, Scalar};
+use crate::manual::core::DataType;
+use crate::{core, Error, Result};
+
+use super::{col_count_i32, match_length, row_count_i32};
+
+/// OpenCV stores color images in BGR(A) channel order while `image` crate uses RGB(A), this swaps the red and blue channels
+#[inline]
+fn swap_red_blue<P: Pixel>(px: &mut P) {
+	if matches!(P::COLOR_MODEL, "RGB" | "RGBA") {
+		px.channels_mut().swap(0, 2);
+	}
+}
+
+/// Returns the pixel data of the `ImageBuffer` without the trailing data that the container might have
+#[inline]
+fn image_data<P: Pixel>(img: &ImageBuffer<P, impl Deref<Target = [P::Subpixel]>>) -> &[P::Subpixel] {
+	let len = img.width() as usize * img.height() as usize * usize::from(P::CHANNEL_COUNT);
+	&img.as_raw()[..len]
+}
+
+#[inline]
+fn image_rows_cols<P: Pixel>(img: &ImageBuffer<P, impl Deref<Target = [P::Subpixel]>>) -> Result<(i32, i32)> {
+	Ok((row_count_i32(img.height() as usize)?, col_count_i32(img.width() as usize)?))
+}
+
+fn match_pixel<P: Pixel + DataType>(mat: &(impl MatTraitConst + ?Sized)) -> Result<()> {
+	if mat.depth() != P::opencv_depth() {
+		#[cfg(not(ocvrs_opencv_branch_32))]
+		let depth = core::depth_to_string(mat.depth())?;
+		#[cfg(ocvrs_opencv_branch_32)]
+		let depth = mat.depth();
+		return Err(Error::new(
+			core::StsUnmatchedFormats,
+			format!(
+				"Mat depth: {depth} doesn't match the subpixel type of the {} image",
+				P::COLOR_MODEL
+			),
+		));
+	}
+	if mat.channels() != P::opencv_channels() {
+		return Err(Error::new(
+			core::BadNumChannels,
+			format!(
+				"Mat channel count: {} doesn't match the channel count: {} of the {} image",
+				mat.channels(),
+				P::CHANNEL_COUNT,
+				P::COLOR_MODEL
+			),
+		));
+	}
+	if mat.dims() > 2 {
+		return Err(Error::new(
+			core::StsUnmatchedSizes,
+			format!("Mat must have 2 dimensions to be converted to an image, but it has: {}", mat.dims()),
+		));
+	}
+	Ok(())
+}
+
+impl Mat {
+	/// Create a new `Mat` that references the pixel data of the `image` crate [ImageBuffer]
+	///
+	/// No data is copied so the channel order of the image is preserved, e.g. [image::RgbImage] results in a `Mat` with RGB
+	/// channel order. Use `Mat::try_from()` if you need a `Mat` with BGR channel order that most OpenCV functions expect.
+	#[inline]
+	pub fn from_image_buffer<P, C>(img: &ImageBuffer<P, C>) -> Result<BoxedRef<Self>>
+	where
+		P: Pixel + DataType,
+		C: Deref<Target = [P::Subpixel]>,
+	{
+		let (rows, cols) = image_rows_cols(img)?;
+		let data = image_data(img);
+		match_length(&[rows, cols], data.len(), usize::from(P::CHANNEL_COUNT))?;
+		let m = unsafe {
+			Self::new_rows_cols_with_data_unsafe_def(rows, cols, P::opencv_type(), data.as_ptr().cast::<c_void>().cast_mut())
+		}?;
+		Ok(<BoxedRef<Mat>>::from(m))
+	}
+
+	/// Create a new mutable `Mat` that references the pixel data of the `image` crate [ImageBuffer], see
+	/// [Mat::from_image_buffer]
+	#[inline]
+	pub fn from_image_buffer_mut<P, C>(img: &mut ImageBuffer<P, C>) -> Result<BoxedRefMut<Self>>
+	where
+		P: Pixel + DataType,
+		C: DerefMut<Target = [P::Subpixel]>,
+	{
+		let (rows, cols) = image_rows_cols(img)?;
+		let len = image_data(img).len();
+		match_length(&[rows, cols], len, usize::from(P::CHANNEL_COUNT))?;
+		let data = &mut DerefMut::deref_mut(img)[..len];
+		let m =
+			unsafe { Self::new_rows_cols_with_data_unsafe_def(rows, cols, P::opencv_type(), data.as_mut_ptr().cast::<c_void>()) }?;
+		Ok(<BoxedRefMut<Mat>>::from(m))
+	}
+
+	/// Create a new `Mat` that references the pixel data of the `image` crate [DynamicImage], see [Mat::from_image_buffer]
+	pub fn from_dynamic_image(img: &DynamicImage) -> Result<BoxedRef<Self>> {
+		match img {
+			DynamicImage::ImageLuma8(img) => Self::from_image_buffer(img),
+			DynamicImage::ImageLumaA8(img) => Self::from_image_buffer(img),
+			DynamicImage::ImageRgb8(img) => Self::from_image_buffer(img),
+			DynamicImage::ImageRgba8(img) => Self::from_image_buffer(img),
+			DynamicImage::ImageLuma16(img) => Self::from_image_buffer(img),
+			DynamicImage::ImageLumaA16(img) => Self::from_image_buffer(img),
+			DynamicImage::ImageRgb16(img) => Self::from_image_buffer(img),
+			DynamicImage::ImageRgba16(img) => Self::from_image_buffer(img),
+			DynamicImage::ImageRgb32F(img) => Self::from_image_buffer(img),
+			DynamicImage::ImageRgba32F(img) => Self::from_image_buffer(img),
+			_ => Err(Error::new(core::StsUnsupportedFormat, "Unsupported DynamicImage variant")),
+		}
+	}
+}
+
+impl<P, C> TryFrom<&ImageBuffer<P, C>> for Mat
+where
+	P: Pixel + DataType,
+	C: Deref<Target = [P::Subpixel]>,
+{
+	type Error = Error;
+
+	/// Copies the image into a new `Mat` converting RGB(A) channel order to BGR(A)
+	fn try_from(img: &ImageBuffer<P, C>) -> Result<Self> {
+		let (rows, cols) = image_rows_cols(img)?;
+		let mut out = Self::new_rows_cols_with_default(rows, cols, P::opencv_type(), Scalar::all(0.))?;
+		for (dst, src) in out.data_typed_mut::<P>()?.iter_mut().zip(img.pixels()) {
+			*dst = *src;
+			swap_red_blue(dst);
+		}
+		Ok(out)
+	}
+}
+
+impl TryFrom<&DynamicImage> for Mat {
+	type Error = Error;
+
+	/// Copies the image into a new `Mat` converting RGB(A) channel order to BGR(A)
+	fn try_from(img: &DynamicImage) -> Result<Self> {
+		match img {
+			DynamicImage::ImageLuma8(img) => Self::try_from(img),
+			DynamicImage::ImageLumaA8(img) => Self::try_from(img),
+			DynamicImage::ImageRgb8(img) => Self::try_from(img),
+			DynamicImage::ImageRgba8(img) => Self::try_from(img),
+			DynamicImage::ImageLuma16(img) => Self::try_from(img),
+			DynamicImage::ImageLumaA16(img) => Self::try_from(img),
+			DynamicImage::ImageRgb16(img) => Self::try_from(img),
+			DynamicImage::ImageRgba16(img) => Self::try_from(img),
+			DynamicImage::ImageRgb32F(img) => Self::try_from(img),
+			DynamicImage::ImageRgba32F(img) => Self::try_from(img),
+			_ => Err(Error::new(core::StsUnsupportedFormat, "Unsupported DynamicImage variant")),
+		}
+	}
+}
+
+impl<P: Pixel + DataType> TryFrom<&Mat> for ImageBuffer<P, Vec<P::Subpixel>> {
+	type Error = Error;
+
+	/// Copies the `Mat` into a new image converting BGR(A) channel order to RGB(A)
+	///
+	/// `Mat` must be 2-dimensional and its depth and channel count must match the pixel type. `Mat` doesn't need to be
+	/// continuous.
+	fn try_from(mat: &Mat) -> Result<Self> {
+		match_pixel::<P>(mat)?;
+		// safe because Mat::rows() and Mat::cols() can't be negative
+		let (height, width) = (mat.rows() as u32, mat.cols() as u32);
+		let mut data = Vec::with_capacity(mat.total() * usize::from(P::CHANNEL_COUNT));
+		for row in 0..mat.rows() {
+			for px in mat.at_row::<P>(row)? {
+				let mut px = *px;
+				swap_red_blue(&mut px);
+				data.extend_from_slice(px.channels());
+			}
+		}
+		Self::from_raw(width, height, data)
+			.ok_or_else(|| Error::new(core::StsUnmatchedSizes, "Mat data doesn't match the image size"))
+	}
+}
+
+impl TryFrom<&Mat> for DynamicImage {
+	type Error = Error;
+
+	/// Copies the `Mat` into a new image of the matching type converting BGR(A) channel order to RGB(A)
+	///
+	/// Supported `Mat` depths are `CV_8U` and `CV_16U` with 1 to 4 channels and `CV_32F` with 3 or 4 channels.
+	fn try_from(mat: &Mat) -> Result<Self> {
+		Ok(match (mat.depth(), mat.channels()) {
+			(core::CV_8U, 1) => Self::ImageLuma8(mat.try_into()?),
+			(core::CV_8U, 2) => Self::ImageLumaA8(mat.try_into()?),
+			(core::CV_8U, 3) => Self::ImageRgb8(mat.try_into()?),
+			(core::CV_8U, 4) => Self::ImageRgba8(mat.try_into()?),
+			(core::CV_16U, 1) => Self::ImageLuma16(mat.try_into()?),
+			(core::CV_16U, 2) => Self::ImageLumaA16(mat.try_into()?),
+			(core::CV_16U, 3) => Self::ImageRgb16(mat.try_into()?),
+			(core::CV_16U, 4) => Self::ImageRgba16(mat.try_into()?),
+			(core::CV_32F, 3) => Self::ImageRgb32F(mat.try_into()?),
+			(core::CV_32F, 4) => Self::ImageRgba32F(mat.try_into()?),
+			(depth, channels) => {
+				#[cfg(not(ocvrs_opencv_branch_32))]
+				let depth = core::depth_to_string(depth)?;
+				return Err(Error::new(
+					core::StsUnsupportedFormat,
+					format!("Mat with depth: {depth} and channel count: {channels} can't be represented as DynamicImage"),
+				));
+			}
+		})
+	}
+}
@@ -0,0 +1,149 @@
+use std::ffi::c_void;
+use std::fs::File;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+use std::{ptr, slice};
+
+use crate::boxed_ref::BoxedRef;
+use crate::core::{Mat, CV_MAT_DEPTH};
+use crate::traits::Boxed;
+use crate::{core, sys, Error, Result};
+
+type ReleaseFn = unsafe extern "C" fn(ctx: *mut c_void);
+
+extern "C" {
+	fn cv_Mat_fromExternalData_int_const_intX_int_voidX_voidX_ReleaseFn(
+		ndims: i32,
+		sizes: *const i32,
+		typ: i32,
+		data: *mut c_void,
+		ctx: *mut c_void,
+		release: ReleaseFn,
+		ocvrs_return: *mut sys::Result<*mut c_void>,
+	);
+}
+
+unsafe extern "C" fn release_mapping(ctx: *mut c_void) {
+	drop(Box::from_raw(ctx.cast::<FileMapping>()));
+}
+
+/// Private read-only memory mapping of the whole file
+pub(super) struct FileMapping {
+	ptr: *mut u8,
+	len: usize,
+}
+
+// the mapping is private and is only accessed through the `Mat` that owns it
+unsafe impl Send for FileMapping {}
+
+unsafe impl Sync for FileMapping {}
+
+impl FileMapping {
+	pub fn open(path: &Path) -> Result<Self> {
+		let file = File::open(path).map_err(|e| Error::new(core::StsError, format!("Can't open {}: {e}", path.display())))?;
+		let len = file
+			.metadata()
+			.map_err(|e| Error::new(core::StsError, format!("Can't query the size of {}: {e}", path.display())))?
+			.len();
+		let len = usize::try_from(len).map_err(|_| {
+			Error::new(
+				core::StsOutOfRange,
+				format!("File {} is too big to be mapped", path.display()),
+			)
+		})?;
+		if len == 0 {
+			return Err(Error::new(core::StsBadArg, format!("File {} is empty", path.display())));
+		}
+		// the mapping is read-only, the returned `Mat` only gives out the shared references to the data; the mapping stays valid
+		// after the file is closed
+		let ptr = unsafe { libc::mmap(ptr::null_mut(), len, libc::PROT_READ, libc::MAP_PRIVATE, file.as_raw_fd(), 0) };
+		if ptr == libc::MAP_FAILED {
+			return Err(Error::new(
+				core::StsError,
+				format!("Can't map {}: {}", path.display(), std::io::Error::last_os_error()),
+			));
+		}
+		Ok(Self { ptr: ptr.cast(), len })
+	}
+
+	#[inline]
+	pub fn bytes(&self) -> &[u8] {
+		unsafe { slice::from_raw_parts(self.ptr, self.len) }
+	}
+
+	/// Creates a `Mat` over the part of the mapping starting at `offset`, the mapping is released together with the last
+	/// `Mat` referencing it
+	pub fn into_mat(self, offset: usize, sizes: &[i32], typ: i32) -> Result<BoxedRef<'static, Mat>> {
+		let mut data_len = core::get_elem_size(typ)?;
+		for (i, size) in sizes.iter().enumerate() {
+			let size = usize::try_from(*size)
+				.map_err(|_| Error::new(core::StsOutOfRange, format!("Dimension {i} must not be negative")))?;
+			data_len = data_len.saturating_mul(size);
+		}
+		if sizes.is_empty() || data_len == 0 {
+			return Err(Error::new(core::StsBadArg, "Empty arrays can't be mapped"));
+		}
+		if offset.saturating_add(data_len) > self.len {
+			return Err(Error::new(
+				core::StsUnmatchedSizes,
+				format!(
+					"Data of {data_len} bytes at offset {offset} doesn't fit in the file of {} bytes",
+					self.len
+				),
+			));
+		}
+		if offset % core::get_elem_size(CV_MAT_DEPTH(typ))? != 0 {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!("Data offset {offset} is not aligned to the element size"),
+			));
+		}
+		let dims = i32::try_from(sizes.len()).map_err(|_| Error::new(core::StsOutOfRange, "Too many dimensions"))?;
+		let data = unsafe { self.ptr.add(offset) }.cast::<c_void>();
+		let ctx = Box::into_raw(Box::new(self)).cast::<c_void>();
+		return_send!(via ocvrs_return);
+		unsafe {
+			cv_Mat_fromExternalData_int_const_intX_int_voidX_voidX_ReleaseFn(
+				dims,
+				sizes.as_ptr(),
+				typ,
+				data,
+				ctx,
+				release_mapping,
+				ocvrs_return.as_mut_ptr(),
+			)
+		};
+		return_receive!(unsafe ocvrs_return => ret);
+		match ret.into_result() {
+			// safe because the returned pointer is a newly allocated `Mat` that owns the mapping
+			Ok(ret) => Ok(BoxedRef::from(unsafe { Mat::from_raw(ret) })),
+			Err(e) => {
+				// the ownership of the mapping is only taken on success
+				unsafe { release_mapping(ctx) };
+				Err(e)
+			}
+		}
+	}
+}
+
+impl Drop for FileMapping {
+	fn drop(&mut self) {
+		unsafe { libc::munmap(self.ptr.cast(), self.len) };
+	}
+}
+
+impl Mat {
+	/// Memory-maps the file with the raw `Mat` data without copying it
+	///
+	/// The data starts at `offset` bytes from the file start and must be continuous, the file can be bigger than the data.
+	/// The returned `Mat` owns the read-only mapping, it's unmapped when the last `Mat` referencing the data is dropped. Use
+	/// `try_clone()` to get a modifiable copy of the data. See also [Mat::map_npy].
+	///
+	/// # Safety
+	/// The file must not be modified or truncated while the returned `Mat` (or any `Mat` referencing its data) is alive, same as
+	/// for any other memory mapping. The changes to the file can be visible through the `Mat` and accessing the data past the
+	/// end of the truncated file terminates the process with `SIGBUS`.
+	pub unsafe fn map_raw(path: impl AsRef<Path>, sizes: &[i32], typ: i32, offset: usize) -> Result<BoxedRef<'static, Mat>> {
+		FileMapping::open(path.as_ref())?.into_mat(offset, sizes, typ)
+	}
+}
@@ -154,6 +154,20 @@ impl<T: DataType> Mat_<T> {
 		match_is_continuous(self)?;
 		unsafe { self.data_typed_unchecked_mut() }
 	}
+
+	/// See [Mat::as_array_view]
+	#[cfg(feature = "ndarray")]
+	#[inline]
+	pub fn as_array_view(&self) -> Result<ndarray::ArrayViewD<'_, T>> {
+		MatTraitConstManual::as_array_view(self)
+	}
+
+	/// See [Mat::as_array_view_mut]
+	#[cfg(feature = "ndarray")]
+	#[inline]
+	pub fn as_array_view_mut(&mut self) -> Result<ndarray::ArrayViewMutD<'_, T>> {
+		MatTraitManual::as_array_view_mut(self)
+	}
 }
 
 impl<T> MatTraitConst for Mat_<T> {
@@ -0,0 +1,209 @@
+use std::ffi::c_void;
+use std::mem;
+
+use ndarray::{ArrayBase, ArrayViewD, ArrayViewMutD, Data, DataMut, Dimension, IxDyn, ShapeBuilder};
+
+use crate::boxed_ref::{BoxedRef, BoxedRefMut};
+use crate::core::{Mat, MatTrait, MatTraitConst, CV_MAKETYPE};
+use crate::manual::core::DataType;
+use crate::{core, Error, Result};
+
+/// Calculates the shape and the strides (in units of `T`) of the `ndarray` view over the `Mat` data
+fn view_layout<T: DataType>(mat: &(impl MatTraitConst + ?Sized)) -> Result<(Vec<usize>, Vec<usize>)> {
+	let channels = mat.channels();
+	if T::opencv_depth() != mat.depth() || (T::opencv_channels() != channels && T::opencv_channels() != 1) {
+		return Err(Error::new(
+			core::StsUnmatchedFormats,
+			format!(
+				"Mat depth: {} and channels: {channels} can't be viewed as array of elements with depth: {} and channels: {}",
+				mat.depth(),
+				T::opencv_depth(),
+				T::opencv_channels()
+			),
+		));
+	}
+	let elem_size = mem::size_of::<T>();
+	let elem_size1 = mat.elem_size1();
+	let mat_size = mat.mat_size();
+	let mut shape = Vec::with_capacity(mat_size.len() + 1);
+	let mut strides = Vec::with_capacity(mat_size.len() + 1);
+	for (i, &size) in mat_size.iter().enumerate() {
+		// safe because Mat dimensions can't be negative and there can't be more than i32::MAX of them
+		shape.push(size as usize);
+		let step = mat.step1(i as i32)? * elem_size1;
+		if step % elem_size != 0 {
+			return Err(Error::new(
+				core::BadStep,
+				format!("Step: {step} along dimension: {i} is not a multiple of the element size: {elem_size}"),
+			));
+		}
+		strides.push(step / elem_size);
+	}
+	if T::opencv_channels() != channels {
+		// safe because Mat channel count is always positive
+		shape.push(channels as usize);
+		strides.push(1);
+	}
+	Ok((shape, strides))
+}
+
+pub(crate) fn array_view<T: DataType>(mat: &(impl MatTraitConst + ?Sized)) -> Result<ArrayViewD<'_, T>> {
+	let (shape, strides) = view_layout::<T>(mat)?;
+	let data = mat.data();
+	Ok(if data.is_null() {
+		ArrayViewD::from_shape(IxDyn(&[0]), &[]).expect("Empty shape always matches empty slice")
+	} else {
+		// safe because the shape and the strides are taken from the Mat that owns the data and the type is checked
+		unsafe { ArrayViewD::from_shape_ptr(IxDyn(&shape).strides(IxDyn(&strides)), data.cast::<T>()) }
+	})
+}
+
+pub(crate) fn array_view_mut<T: DataType>(mat: &mut (impl MatTrait + ?Sized)) -> Result<ArrayViewMutD<'_, T>> {
+	let (shape, strides) = view_layout::<T>(mat)?;
+	let data = mat.data_mut();
+	Ok(if data.is_null() {
+		ArrayViewMutD::from_shape(IxDyn(&[0]), &mut []).expect("Empty shape always matches empty slice")
+	} else {
+		// safe because the shape and the strides are taken from the Mat that owns the data and the type is checked
+		unsafe { ArrayViewMutD::from_shape_ptr(IxDyn(&shape).strides(IxDyn(&strides)), data.cast::<T>()) }
+	})
+}
+
+/// Calculates the sizes, the steps (in bytes) and the type of the `Mat` that references the data of an `ndarray` array
+///
+/// When `trailing_channels` is `true` the last axis of the array is treated as the channel axis.
+fn mat_layout<T: DataType>(shape: &[usize], strides: &[isize], trailing_channels: bool) -> Result<(Vec<i32>, Vec<usize>, i32)> {
+	let (shape, strides, typ, elem_size) = if trailing_channels {
+		if T::opencv_channels() != 1 {
+			return Err(Error::new(
+				core::BadNumChannels,
+				format!(
+					"Array element must have a single channel when using trailing channel axis, but it has: {}",
+					T::opencv_channels()
+				),
+			));
+		}
+		if shape.len() < 2 {
+			return Err(Error::new(
+				core::StsBadSize,
+				"Array must have at least 2 dimensions when using trailing channel axis",
+			));
+		}
+		let (&channels, shape) = shape.split_last().expect("Checked above");
+		let (&channel_stride, strides) = strides.split_last().expect("Array strides always match its shape");
+		let channels_i32 = i32::try_from(channels)
+			.ok()
+			.filter(|cn| (1..=core::CV_CN_MAX).contains(cn))
+			.ok_or_else(|| {
+				Error::new(
+					core::BadNumChannels,
+					format!("Channel count: {channels} must be within 1..={}", core::CV_CN_MAX),
+				)
+			})?;
+		if channels > 1 && channel_stride != 1 {
+			return Err(Error::new(core::BadStep, "Channel axis of the array must be contiguous"));
+		}
+		(
+			shape,
+			strides,
+			CV_MAKETYPE(T::opencv_depth(), channels_i32),
+			mem::size_of::<T>() * channels,
+		)
+	} else {
+		(shape, strides, T::opencv_type(), mem::size_of::<T>())
+	};
+	if shape.is_empty() {
+		return Err(Error::new(core::StsBadSize, "Array must have at least 1 dimension"));
+	}
+	let sizes = shape
+		.iter()
+		.map(|&size| {
+			i32::try_from(size).map_err(|_| Error::new(core::StsOutOfRange, format!("Dimension size: {size} is too high")))
+		})
+		.collect::<Result<Vec<_>>>()?;
+	let mut steps = vec![0; shape.len()];
+	let mut contiguous_step = elem_size;
+	for (i, (&size, &stride)) in shape.iter().zip(strides).enumerate().rev() {
+		// ndarray doesn't guarantee any particular stride for axes of length 1 or less, so we use the contiguous one
+		let step = if size <= 1 {
+			contiguous_step
+		} else {
+			usize::try_from(stride)
+				.ok()
+				.filter(|&stride| stride > 0)
+				.map(|stride| stride * mem::size_of::<T>())
+				.ok_or_else(|| Error::new(core::BadStep, format!("Stride: {stride} along axis: {i} must be positive")))?
+		};
+		if i == shape.len() - 1 && step != elem_size {
+			return Err(Error::new(core::BadStep, "Last axis of the array must be contiguous"));
+		}
+		steps[i] = step;
+		contiguous_step = step * size.max(1);
+	}
+	// OpenCV expects ndims-1 steps, the last one is always the element size
+	steps.pop();
+	Ok((sizes, steps, typ))
+}
+
+impl Mat {
+	/// Create a new `Mat` that references the data of an `ndarray` array, every axis of the array becomes a `Mat` dimension
+	///
+	/// The last axis of the array must be contiguous, other axes can have arbitrary positive strides (e.g. when the array
+	/// is a slice of a bigger one).
+	#[inline]
+	pub fn from_ndarray<T: DataType, S: Data<Elem = T>, D: Dimension>(array: &ArrayBase<S, D>) -> Result<BoxedRef<'_, Self>> {
+		mat_from_array(array, false)
+	}
+
+	/// Create a new mutable `Mat` that references the data of an `ndarray` array, see [Mat::from_ndarray]
+	#[inline]
+	pub fn from_ndarray_mut<T: DataType, S: DataMut<Elem = T>, D: Dimension>(
+		array: &mut ArrayBase<S, D>,
+	) -> Result<BoxedRefMut<'_, Self>> {
+		mat_from_array_mut(array, false)
+	}
+
+	/// Create a new `Mat` that references the data of an `ndarray` array treating its last axis as channels
+	///
+	/// E.g. an `Array3<u8>` with shape `(480, 640, 3)` becomes a 480x640 `Mat` of type `CV_8UC3`.
+	#[inline]
+	pub fn from_ndarray_channels<T: DataType, S: Data<Elem = T>, D: Dimension>(
+		array: &ArrayBase<S, D>,
+	) -> Result<BoxedRef<'_, Self>> {
+		mat_from_array(array, true)
+	}
+
+	/// Create a new mutable `Mat` that references the data of an `ndarray` array treating its last axis as channels, see
+	/// [Mat::from_ndarray_channels]
+	#[inline]
+	pub fn from_ndarray_channels_mut<T: DataType, S: DataMut<Elem = T>, D: Dimension>(
+		array: &mut ArrayBase<S, D>,
+	) -> Result<BoxedRefMut<'_, Self>> {
+		mat_from_array_mut(array, true)
+	}
+}
+
+fn mat_from_array<T: DataType, S: Data<Elem = T>, D: Dimension>(
+	array: &ArrayBase<S, D>,
+	trailing_channels: bool,
+) -> Result<BoxedRef<'_, Mat>> {
+	let (sizes, steps, typ) = mat_layout::<T>(array.shape(), array.strides(), trailing_channels)?;
+	let m = unsafe {
+		Mat::new_nd_with_data_unsafe(
+			&sizes,
+			typ,
+			array.as_ptr().cast::<c_void>().cast_mut(),
+			Some(steps.as_slice()),
+		)
+	}?;
+	Ok(<BoxedRef<Mat>>::from(m))
+}
+
+fn mat_from_array_mut<T: DataType, S: DataMut<Elem = T>, D: Dimension>(
+	array: &mut ArrayBase<S, D>,
+	trailing_channels: bool,
+) -> Result<BoxedRefMut<'_, Mat>> {
+	let (sizes, steps, typ) = mat_layout::<T>(array.shape(), array.strides(), trailing_channels)?;
+	let m = unsafe { Mat::new_nd_with_data_unsafe(&sizes, typ, array.as_mut_ptr().cast::<c_void>(), Some(steps.as_slice())) }?;
+	Ok(<BoxedRefMut<Mat>>::from(m))
+}
@@ -0,0 +1,209 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+#[cfg(unix)]
+use crate::boxed_ref::BoxedRef;
+use crate::core::{Mat, MatTraitConst, MatTraitConstManual, CV_CN_MAX, CV_MAKETYPE, CV_MAT_DEPTH};
+use crate::{core, Error, Result};
+
+#[cfg(unix)]
+use super::mapped::FileMapping;
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+/// Total length of the header including the magic string is padded to this alignment
+const HEADER_ALIGN: usize = 64;
+#[cfg(target_endian = "little")]
+const NATIVE_BYTE_ORDER: char = '<';
+#[cfg(target_endian = "big")]
+const NATIVE_BYTE_ORDER: char = '>';
+
+/// Shape and type of the array parsed from the `.npy` header
+#[cfg_attr(not(unix), allow(dead_code))]
+struct NpyHeader {
+	sizes: Vec<i32>,
+	typ: i32,
+	data_offset: usize,
+}
+
+fn depth_descr(depth: i32) -> Result<&'static str> {
+	Ok(match depth {
+		core::CV_8U => "u1",
+		core::CV_8S => "i1",
+		core::CV_16U => "u2",
+		core::CV_16S => "i2",
+		core::CV_32S => "i4",
+		core::CV_32F => "f4",
+		core::CV_64F => "f8",
+		#[cfg(not(ocvrs_opencv_branch_32))]
+		core::CV_16F => "f2",
+		_ => {
+			return Err(Error::new(
+				core::StsUnsupportedFormat,
+				format!("Mat depth: {depth} can't be stored in .npy"),
+			))
+		}
+	})
+}
+
+#[cfg_attr(not(unix), allow(dead_code))]
+fn descr_depth(descr: &str) -> Result<i32> {
+	let unsupported = || Error::new(core::StsUnsupportedFormat, format!("Unsupported .npy data type: {descr}"));
+	let (byte_order, typ) = descr.split_at(descr.chars().next().map_or(0, char::len_utf8));
+	let depth = match typ {
+		"u1" | "b1" => core::CV_8U,
+		"i1" => core::CV_8S,
+		"u2" => core::CV_16U,
+		"i2" => core::CV_16S,
+		"i4" => core::CV_32S,
+		"f4" => core::CV_32F,
+		"f8" => core::CV_64F,
+		#[cfg(not(ocvrs_opencv_branch_32))]
+		"f2" => core::CV_16F,
+		_ => return Err(unsupported()),
+	};
+	match byte_order.chars().next() {
+		Some('|' | '=') => Ok(depth),
+		Some(c) if c == NATIVE_BYTE_ORDER => Ok(depth),
+		// the byte order doesn't matter for single byte types
+		Some('<' | '>') if typ.ends_with('1') => Ok(depth),
+		_ => Err(unsupported()),
+	}
+}
+
+/// Returns the literal value of the `key` in the header dictionary
+#[cfg_attr(not(unix), allow(dead_code))]
+fn dict_value<'h>(dict: &'h str, key: &str) -> Result<&'h str> {
+	let missing = || Error::new(core::StsParseError, format!("Missing or invalid {key} in the .npy header"));
+	let value_start = [format!("'{key}'"), format!("\"{key}\"")]
+		.iter()
+		.find_map(|quoted_key| dict.find(quoted_key.as_str()).map(|pos| pos + quoted_key.len()))
+		.ok_or_else(missing)?;
+	let value = dict[value_start..]
+		.trim_start()
+		.strip_prefix(':')
+		.ok_or_else(missing)?
+		.trim_start();
+	let value_end = match value.chars().next() {
+		Some('(') => value.find(')').map(|pos| pos + 1),
+		Some(quote @ ('\'' | '"')) => value[1..].find(quote).map(|pos| pos + 2),
+		_ => value.find([',', '}']),
+	}
+	.ok_or_else(missing)?;
+	Ok(value[..value_end].trim_end())
+}
+
+#[cfg_attr(not(unix), allow(dead_code))]
+fn parse_header(bytes: &[u8]) -> Result<NpyHeader> {
+	let invalid = |msg: &str| Error::new(core::StsParseError, format!("Invalid .npy header: {msg}"));
+	if !bytes.starts_with(MAGIC) || bytes.len() < MAGIC.len() + 4 {
+		return Err(invalid("bad magic"));
+	}
+	let major_version = bytes[MAGIC.len()];
+	let (len_start, len_size) = match major_version {
+		1 => (MAGIC.len() + 2, 2),
+		2 | 3 => (MAGIC.len() + 2, 4),
+		_ => return Err(invalid(&format!("unsupported version {major_version}"))),
+	};
+	let header_len = bytes
+		.get(len_start..len_start + len_size)
+		.ok_or_else(|| invalid("truncated"))?
+		.iter()
+		.rev()
+		.fold(0, |acc, b| acc << 8 | usize::from(*b));
+	let data_offset = len_start + len_size + header_len;
+	let dict = bytes
+		.get(len_start + len_size..data_offset)
+		.ok_or_else(|| invalid("truncated"))?;
+	let dict = std::str::from_utf8(dict).map_err(|_| invalid("not a valid text"))?;
+
+	let descr = dict_value(dict, "descr")?;
+	let depth = descr_depth(descr.trim_matches(['\'', '"']))?;
+	let shape = dict_value(dict, "shape")?;
+	let mut sizes = shape
+		.strip_prefix('(')
+		.and_then(|shape| shape.strip_suffix(')'))
+		.ok_or_else(|| invalid(&format!("bad shape {shape}")))?
+		.split(',')
+		.map(str::trim)
+		.filter(|size| !size.is_empty())
+		.map(|size| size.parse::<i32>().map_err(|_| invalid(&format!("bad shape {shape}"))))
+		.collect::<Result<Vec<_>>>()?;
+	if sizes.is_empty() {
+		// scalar
+		sizes.push(1);
+	}
+	if dict_value(dict, "fortran_order")? != "False" && sizes.iter().filter(|&&size| size > 1).count() > 1 {
+		return Err(Error::new(
+			core::StsUnsupportedFormat,
+			"Arrays in the Fortran order are not supported",
+		));
+	}
+	// same as in the OpenCV Python bindings: 3-dimensional array is treated as a multichannel 2-dimensional one
+	let mut channels = 1;
+	if sizes.len() == 3 && sizes[2] <= CV_CN_MAX {
+		channels = sizes.pop().unwrap_or(1);
+	}
+	Ok(NpyHeader {
+		sizes,
+		typ: CV_MAKETYPE(depth, channels),
+		data_offset,
+	})
+}
+
+pub(super) fn write(mat: &(impl MatTraitConst + ?Sized), mut writer: impl Write) -> Result<()> {
+	if mat.empty() {
+		return Err(Error::new(core::StsBadArg, "Empty Mat can't be stored in .npy"));
+	}
+	let data = mat.data_bytes()?;
+	let mut shape = mat.mat_size().iter().map(i32::to_string).collect::<Vec<_>>();
+	let channels = mat.channels();
+	if channels > 1 {
+		shape.push(channels.to_string());
+	}
+	let shape = if shape.len() == 1 {
+		format!("({},)", shape[0])
+	} else {
+		format!("({})", shape.join(", "))
+	};
+	let mut header = format!(
+		"{{'descr': '{NATIVE_BYTE_ORDER}{}', 'fortran_order': False, 'shape': {shape}, }}",
+		depth_descr(CV_MAT_DEPTH(mat.typ()))?
+	);
+	// magic, version (2 bytes) and header length (2 bytes) precede the header, it's terminated by the newline
+	let prefix_len = MAGIC.len() + 4;
+	let padding = (HEADER_ALIGN - (prefix_len + header.len() + 1) % HEADER_ALIGN) % HEADER_ALIGN;
+	header.push_str(&" ".repeat(padding));
+	header.push('\n');
+	let header_len = u16::try_from(header.len()).map_err(|_| Error::new(core::StsOutOfRange, "Too many dimensions"))?;
+	let io_error = |e| Error::new(core::StsError, format!("Can't write .npy data: {e}"));
+	writer.write_all(MAGIC).map_err(io_error)?;
+	writer.write_all(&[1, 0]).map_err(io_error)?;
+	writer.write_all(&header_len.to_le_bytes()).map_err(io_error)?;
+	writer.write_all(header.as_bytes()).map_err(io_error)?;
+	writer.write_all(data).map_err(io_error)?;
+	writer.flush().map_err(io_error)
+}
+
+pub(super) fn write_file(mat: &(impl MatTraitConst + ?Sized), path: &Path) -> Result<()> {
+	let file = File::create(path).map_err(|e| Error::new(core::StsError, format!("Can't create {}: {e}", path.display())))?;
+	write(mat, BufWriter::new(file))
+}
+
+#[cfg(unix)]
+impl Mat {
+	/// Memory-maps the NumPy `.npy` file without copying the data
+	///
+	/// The shape and the type of the `Mat` are taken from the file header, like in the OpenCV Python bindings a 3-dimensional
+	/// array with the last dimension of at most `CV_CN_MAX` is loaded as a 2-dimensional multichannel `Mat`. Only the arrays
+	/// in the C order and with the native byte order are supported. The returned `Mat` owns the read-only mapping, see
+	/// [Mat::map_raw] for details.
+	///
+	/// # Safety
+	/// Same as [Mat::map_raw], the file must not be modified or truncated while the data is mapped.
+	pub unsafe fn map_npy(path: impl AsRef<Path>) -> Result<BoxedRef<'static, Mat>> {
+		let mapping = FileMapping::open(path.as_ref())?;
+		let header = parse_header(mapping.bytes())?;
+		mapping.into_mat(header.data_offset, &header.sizes, header.typ)
+	}
+}
@@ -0,0 +1,20 @@
+use rayon::iter::{Flatten, IntoParallelIterator, ParallelIterator};
+use rayon::vec::IntoIter;
+
+use crate::core::{MatTrait, MatTraitConst};
+use crate::manual::core::DataType;
+use crate::Result;
+
+use super::rows::{MatRowsIter, MatRowsIterMut};
+
+pub(crate) fn par_rows<T: DataType + Sync>(mat: &(impl MatTraitConst + ?Sized)) -> Result<IntoIter<&[T]>> {
+	Ok(MatRowsIter::new(mat)?.collect::<Vec<_>>().into_par_iter())
+}
+
+pub(crate) fn par_rows_mut<T: DataType + Send>(mat: &mut (impl MatTrait + ?Sized)) -> Result<IntoIter<&mut [T]>> {
+	Ok(MatRowsIterMut::new(mat)?.collect::<Vec<_>>().into_par_iter())
+}
+
+pub(crate) fn par_pixels_mut<T: DataType + Send>(mat: &mut (impl MatTrait + ?Sized)) -> Result<Flatten<IntoIter<&mut [T]>>> {
+	par_rows_mut(mat).map(ParallelIterator::flatten)
+}
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::{fmt, mem};
+
+use crate::boxed_ref::{BoxedRef, BoxedRefMut};
+use crate::core::{
+	Mat, MatTrait, MatTraitConst, Scalar, Size, ToInputArray, ToInputOutputArray, ToOutputArray, _InputArray, _InputOutputArray,
+	_OutputArray,
+};
+use crate::{sync, Result};
+
+use super::shared_mat::ref_count;
+
+/// Dimension sizes and the type of the pooled `Mat`
+type Shape = (Vec<i32>, i32);
+
+/// Shape of the `Mat` created with the `sizes` and `typ`, same as the `mat_size()` of that `Mat`: OpenCV creates the
+/// 1-dimensional `Mat`s as a single column
+fn shape(sizes: &[i32], typ: i32) -> Shape {
+	if let [rows] = sizes {
+		(vec![*rows, 1], typ)
+	} else {
+		(sizes.to_vec(), typ)
+	}
+}
+
+struct PoolInner {
+	free: Mutex<HashMap<Shape, Vec<Mat>>>,
+	max_per_shape: usize,
+	hits: AtomicUsize,
+	misses: AtomicUsize,
+	returned: AtomicUsize,
+	discarded: AtomicUsize,
+}
+
+impl PoolInner {
+	fn free(&self) -> MutexGuard<HashMap<Shape, Vec<Mat>>> {
+		sync::lock(&self.free)
+	}
+
+	fn recycle(&self, mat: Mat) {
+		// only the continuous `Mat`s exclusively owning their data can be safely reused
+		if !mat.empty() && mat.is_continuous() && ref_count(&mat) == 1 {
+			let shape = (mat.mat_size().to_vec(), mat.typ());
+			let mut free = self.free();
+			let mats = free.entry(shape).or_default();
+			if mats.len() < self.max_per_shape {
+				mats.push(mat);
+				self.returned.fetch_add(1, Ordering::Relaxed);
+				return;
+			}
+		}
+		self.discarded.fetch_add(1, Ordering::Relaxed);
+	}
+}
+
+/// Usage statistics of a [MatPool]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MatPoolStats {
+	/// Number of requests served by a previously allocated `Mat`
+	pub hits: usize,
+	/// Number of requests that needed a new allocation
+	pub misses: usize,
+	/// Number of `Mat`s returned to the pool for reuse
+	pub returned: usize,
+	/// Number of `Mat`s that were not kept by the pool because it was full or because their data was shared or reallocated to
+	/// a non-continuous one
+	pub discarded: usize,
+}
+
+/// Pool of preallocated `Mat`s to avoid reallocating the frame buffers in capture and processing loops
+///
+/// [MatPool::get] returns a [PooledMat] that derefs to `Mat` and can be passed to OpenCV functions as an input or output
+/// array. OpenCV reuses the existing allocation of an output array if its size and type match the output, so e.g. reading into
+/// a pooled `Mat` with `VideoCapture::read` or converting into it with `imgproc::cvt_color` doesn't allocate. When the
+/// [PooledMat] is dropped (possibly in another thread) the `Mat` is returned to the pool. `MatPool` is cheap to clone, all
+/// clones share the same pool.
+/// ```no_run
+/// # use opencv::core::{MatPool, Size, Vec3b};
+/// # use opencv::prelude::*;
+/// # use opencv::{imgproc, videoio};
+/// # fn main() -> opencv::Result<()> {
+/// let pool = MatPool::new(4);
+/// let mut capture = videoio::VideoCapture::new(0, videoio::CAP_ANY)?;
+/// loop {
+///     let mut frame = pool.get(Size::new(640, 480), Vec3b::opencv_type())?;
+///     if !capture.read(&mut frame)? {
+///         break;
+///     }
+///     // process the frame or send it to another thread, it's returned to the pool when dropped
+/// }
+/// println!("{:?}", pool.stats());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct MatPool {
+	inner: Arc<PoolInner>,
+}
+
+impl MatPool {
+	/// Creates a new empty pool that retains at most `max_per_shape` free `Mat`s of every size and type
+	pub fn new(max_per_shape: usize) -> Self {
+		Self {
+			inner: Arc::new(PoolInner {
+				free: Mutex::new(HashMap::new()),
+				max_per_shape,
+				hits: AtomicUsize::new(0),
+				misses: AtomicUsize::new(0),
+				returned: AtomicUsize::new(0),
+				discarded: AtomicUsize::new(0),
+			}),
+		}
+	}
+
+	/// Returns a 2-dimensional `Mat` of the specified size and type, see [MatPool::get_nd]
+	#[inline]
+	pub fn get(&self, size: Size, typ: i32) -> Result<PooledMat> {
+		self.get_nd(&[size.height, size.width], typ)
+	}
+
+	/// Returns a `Mat` with the specified dimension sizes and type
+	///
+	/// The `Mat` is taken from the pool if there is one available, in that case it contains the data left from the previous
+	/// use. Otherwise, a new zero-filled `Mat` is allocated.
+	pub fn get_nd(&self, sizes: &[i32], typ: i32) -> Result<PooledMat> {
+		let reused = self.inner.free().get_mut(&shape(sizes, typ)).and_then(Vec::pop);
+		let mat = if let Some(mat) = reused {
+			self.inner.hits.fetch_add(1, Ordering::Relaxed);
+			mat
+		} else {
+			self.inner.misses.fetch_add(1, Ordering::Relaxed);
+			Mat::new_nd_with_default(sizes, typ, Scalar::all(0.))?
+		};
+		Ok(PooledMat {
+			mat,
+			pool: Arc::clone(&self.inner),
+		})
+	}
+
+	/// Allocates `count` `Mat`s of the specified size and type in advance, limited by the pool capacity
+	pub fn preallocate(&self, size: Size, typ: i32, count: usize) -> Result<()> {
+		let sizes = [size.height, size.width];
+		let count = count.min(self.inner.max_per_shape);
+		let mut mats = Vec::with_capacity(count);
+		for _ in 0..count {
+			mats.push(Mat::new_nd_with_default(&sizes, typ, Scalar::all(0.))?);
+		}
+		let mut free = self.inner.free();
+		let free_mats = free.entry(shape(&sizes, typ)).or_default();
+		let missing = self.inner.max_per_shape.saturating_sub(free_mats.len());
+		free_mats.extend(mats.into_iter().take(missing));
+		Ok(())
+	}
+
+	/// Returns the current usage statistics
+	pub fn stats(&self) -> MatPoolStats {
+		MatPoolStats {
+			hits: self.inner.hits.load(Ordering::Relaxed),
+			misses: self.inner.misses.load(Ordering::Relaxed),
+			returned: self.inner.returned.load(Ordering::Relaxed),
+			discarded: self.inner.discarded.load(Ordering::Relaxed),
+		}
+	}
+
+	/// Number of free `Mat`s currently retained by the pool
+	pub fn len(&self) -> usize {
+		self.inner.free().values().map(Vec::len).sum()
+	}
+
+	/// Returns `true` if the pool doesn't retain any free `Mat`s
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Frees all `Mat`s retained by the pool
+	pub fn clear(&self) {
+		let free = mem::take(&mut *self.inner.free());
+		drop(free);
+	}
+}
+
+impl fmt::Debug for MatPool {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("MatPool")
+			.field("max_per_shape", &self.inner.max_per_shape)
+			.field("len", &self.len())
+			.field("stats", &self.stats())
+			.finish()
+	}
+}
+
+/// `Mat` borrowed from a [MatPool], it's returned to the pool on drop
+pub struct PooledMat {
+	mat: Mat,
+	pool: Arc<PoolInner>,
+}
+
+impl PooledMat {
+	/// Takes the `Mat` out of the pool, it won't be returned there on drop
+	#[inline]
+	pub fn detach(mut self) -> Mat {
+		mem::take(&mut self.mat)
+	}
+}
+
+impl Drop for PooledMat {
+	fn drop(&mut self) {
+		let mat = mem::take(&mut self.mat);
+		// empty `Mat` means that it was detached
+		if !mat.empty() {
+			self.pool.recycle(mat);
+		}
+	}
+}
+
+impl Deref for PooledMat {
+	type Target = Mat;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		&self.mat
+	}
+}
+
+impl DerefMut for PooledMat {
+	#[inline]
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.mat
+	}
+}
+
+impl MatTraitConst for PooledMat {
+	#[inline]
+	fn as_raw_Mat(&self) -> *const c_void {
+		self.mat.as_raw_Mat()
+	}
+}
+
+impl MatTrait for PooledMat {
+	#[inline]
+	fn as_raw_mut_Mat(&mut self) -> *mut c_void {
+		self.mat.as_raw_mut_Mat()
+	}
+}
+
+impl ToInputArray for PooledMat {
+	#[inline]
+	fn input_array(&self) -> Result<BoxedRef<_InputArray>> {
+		self.mat.input_array()
+	}
+}
+
+impl ToOutputArray for PooledMat {
+	#[inline]
+	fn output_array(&mut self) -> Result<BoxedRefMut<_OutputArray>> {
+		self.mat.output_array()
+	}
+}
+
+impl ToInputOutputArray for PooledMat {
+	#[inline]
+	fn input_output_array(&mut self) -> Result<BoxedRefMut<_InputOutputArray>> {
+		self.mat.input_output_array()
+	}
+}
+
+impl fmt::Debug for PooledMat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(&self.mat, f)
+	}
+}
@@ -0,0 +1,181 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::slice;
+
+use crate::core::{MatTrait, MatTraitConst};
+use crate::manual::core::DataType;
+use crate::Result;
+
+use super::match_format;
+
+/// Iterator over the pointers to the starts of `Mat` rows
+///
+/// A row is a run of elements along the last dimension of the `Mat`, it's always contiguous in memory. For a 2-dimensional
+/// `Mat` that's a regular row, for an N-dimensional one there is a row for every combination of indices of the first N-1
+/// dimensions. `Mat` steps are respected so this works for non-continuous `Mat`s too.
+pub(crate) struct RowPtrs {
+	data: *mut u8,
+	/// Sizes of all dimensions but the last one
+	sizes: Vec<usize>,
+	/// Steps in bytes of all dimensions but the last one
+	steps: Vec<usize>,
+	/// Index of the next row along every dimension but the last one
+	idx: Vec<usize>,
+	row_len: usize,
+	remaining: usize,
+}
+
+impl RowPtrs {
+	pub fn new<T: DataType>(mat: &(impl MatTraitConst + ?Sized)) -> Result<Self> {
+		match_format::<T>(mat.typ())?;
+		let mut out = Self {
+			data: mat.data().cast_mut(),
+			sizes: vec![],
+			steps: vec![],
+			idx: vec![],
+			row_len: 0,
+			remaining: 0,
+		};
+		let mat_size = mat.mat_size();
+		if let (Some((&row_len, sizes)), false) = (mat_size.split_last(), out.data.is_null()) {
+			// safe because Mat dimensions can't be negative
+			out.row_len = row_len as usize;
+			out.sizes = sizes.iter().map(|&size| size as usize).collect();
+			let elem_size1 = mat.elem_size1();
+			out.steps = (0..sizes.len())
+				// safe because there can't be more than i32::MAX dimensions
+				.map(|i| mat.step1(i as i32).map(|step| step * elem_size1))
+				.collect::<Result<_>>()?;
+			out.idx = vec![0; sizes.len()];
+			out.remaining = if out.row_len == 0 {
+				0
+			} else {
+				out.sizes.iter().product()
+			};
+		}
+		Ok(out)
+	}
+
+	/// Number of elements in every row
+	#[inline]
+	pub fn row_len(&self) -> usize {
+		self.row_len
+	}
+}
+
+impl Iterator for RowPtrs {
+	type Item = *mut u8;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.remaining == 0 {
+			return None;
+		}
+		let offset = self.idx.iter().zip(&self.steps).map(|(i, step)| i * step).sum();
+		// safe because the offset is within the Mat data as long as the index is within the Mat dimensions
+		let out = unsafe { self.data.add(offset) };
+		self.remaining -= 1;
+		for (i, &size) in self.idx.iter_mut().zip(&self.sizes).rev() {
+			*i += 1;
+			if *i < size {
+				break;
+			}
+			*i = 0;
+		}
+		Some(out)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.remaining, Some(self.remaining))
+	}
+}
+
+impl ExactSizeIterator for RowPtrs {}
+
+/// Iterator over `Mat` rows, see [MatTraitConstManual::rows_iter](crate::core::MatTraitConstManual::rows_iter)
+pub struct MatRowsIter<'m, T> {
+	rows: RowPtrs,
+	_d: PhantomData<&'m T>,
+}
+
+impl<'m, T: DataType> MatRowsIter<'m, T> {
+	pub(crate) fn new(mat: &'m (impl MatTraitConst + ?Sized)) -> Result<Self> {
+		Ok(Self {
+			rows: RowPtrs::new::<T>(mat)?,
+			_d: PhantomData,
+		})
+	}
+}
+
+impl<'m, T: DataType> Iterator for MatRowsIter<'m, T> {
+	type Item = &'m [T];
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let row_len = self.rows.row_len();
+		// safe because the row pointer and the row length are taken from the Mat that owns the data and the type is checked
+		self.rows
+			.next()
+			.map(|row| unsafe { slice::from_raw_parts(row.cast::<T>(), row_len) })
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.rows.size_hint()
+	}
+}
+
+impl<T: DataType> ExactSizeIterator for MatRowsIter<'_, T> {}
+
+impl<T> fmt::Debug for MatRowsIter<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("MatRowsIter")
+			.field("row_len", &self.rows.row_len)
+			.field("remaining", &self.rows.remaining)
+			.finish()
+	}
+}
+
+/// Mutable iterator over `Mat` rows, see [MatTraitManual::rows_iter_mut](crate::core::MatTraitManual::rows_iter_mut)
+pub struct MatRowsIterMut<'m, T> {
+	rows: RowPtrs,
+	_d: PhantomData<&'m mut T>,
+}
+
+impl<'m, T: DataType> MatRowsIterMut<'m, T> {
+	pub(crate) fn new(mat: &'m mut (impl MatTrait + ?Sized)) -> Result<Self> {
+		Ok(Self {
+			rows: RowPtrs::new::<T>(mat)?,
+			_d: PhantomData,
+		})
+	}
+}
+
+impl<'m, T: DataType> Iterator for MatRowsIterMut<'m, T> {
+	type Item = &'m mut [T];
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let row_len = self.rows.row_len();
+		// safe because the rows don't overlap and the Mat is borrowed mutably for the lifetime of the iterator
+		self.rows
+			.next()
+			.map(|row| unsafe { slice::from_raw_parts_mut(row.cast::<T>(), row_len) })
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.rows.size_hint()
+	}
+}
+
+impl<T: DataType> ExactSizeIterator for MatRowsIterMut<'_, T> {}
+
+impl<T> fmt::Debug for MatRowsIterMut<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("MatRowsIterMut")
+			.field("row_len", &self.rows.row_len)
+			.field("remaining", &self.rows.remaining)
+			.finish()
+	}
+}
@@ -0,0 +1,151 @@
+use std::ffi::c_void;
+use std::fmt;
+use std::ops::Deref;
+
+use crate::boxed_ref::BoxedRef;
+use crate::core::{Mat, MatTraitConst, ToInputArray, _InputArray};
+use crate::traits::Boxed;
+use crate::{sys, Result};
+
+extern "C" {
+	fn cv_Mat_refcount_const(instance: *const c_void) -> i32;
+}
+
+/// Number of `Mat` headers referencing the data of `mat`, 0 if the data is not allocated by OpenCV
+#[inline]
+pub(super) fn ref_count(mat: &impl MatTraitConst) -> i32 {
+	unsafe { cv_Mat_refcount_const(mat.as_raw_Mat()) }
+}
+
+/// Shared ownership handle of a `Mat` with the same semantics as copying a `cv::Mat` in C++
+///
+/// Cloning a `SharedMat` creates a new `Mat` header pointing to the same data and bumps the reference counter of that data,
+/// no pixels are copied. The data is only accessible as read-only through [Deref] or [SharedMat::as_mat], the write access
+/// is provided by [SharedMat::make_mut] which performs the copy-on-write: the data is deep-copied only if it's shared with
+/// other `Mat` headers. This allows to cheaply pass a single frame to several consumers, possibly in other threads:
+/// ```no_run
+/// # use opencv::core::{Scalar, SharedMat};
+/// # use opencv::prelude::*;
+/// # fn main() -> opencv::Result<()> {
+/// let frame = SharedMat::new(Mat::new_rows_cols_with_default(480, 640, u8::opencv_type(), Scalar::all(0.))?);
+/// let mut consumer = frame.clone();
+/// std::thread::spawn(move || consumer.make_mut()?.set_scalar(Scalar::all(255.))).join().unwrap()?;
+/// assert_eq!(0, *frame.at_2d::<u8>(0, 0)?);
+/// # Ok(())
+/// # }
+/// ```
+pub struct SharedMat {
+	inner: Mat,
+}
+
+impl SharedMat {
+	/// Wraps the `Mat`, the data of the `Mat` still can be referenced by other `Mat` headers
+	#[inline]
+	pub fn new(mat: Mat) -> Self {
+		Self { inner: mat }
+	}
+
+	/// Same as [SharedMat::clone], but returns an error instead of panicking
+	pub fn try_clone(&self) -> Result<Self> {
+		return_send!(via ocvrs_return);
+		unsafe { sys::cv_Mat_Mat_const_MatR(self.inner.as_raw_Mat(), ocvrs_return.as_mut_ptr()) };
+		return_receive!(unsafe ocvrs_return => ret);
+		let ret = ret.into_result()?;
+		// safe because the returned pointer is a newly allocated `Mat` header owning its own reference to the data
+		Ok(Self::new(unsafe { Mat::from_raw(ret) }))
+	}
+
+	/// Number of `Mat` headers referencing the data of this `SharedMat` (including this one), it's 0 if the data is not
+	/// allocated by OpenCV (e.g. for an empty `Mat` or a `Mat` created over the user data)
+	#[inline]
+	pub fn ref_count(&self) -> i32 {
+		ref_count(&self.inner)
+	}
+
+	/// Returns `true` if this `SharedMat` is the only owner of the data, so it can be safely modified
+	#[inline]
+	pub fn is_unique(&self) -> bool {
+		self.ref_count() == 1
+	}
+
+	/// Returns the read-only view of the underlying `Mat`
+	#[inline]
+	pub fn as_mat(&self) -> &Mat {
+		&self.inner
+	}
+
+	/// Returns the mutable reference to the underlying `Mat` if the data is not shared
+	#[inline]
+	pub fn get_mut(&mut self) -> Option<&mut Mat> {
+		self.is_unique().then_some(&mut self.inner)
+	}
+
+	/// Returns the mutable reference to the underlying `Mat`, the data is deep-copied first if it's shared with other `Mat`
+	/// headers, so the modifications are never visible through them
+	pub fn make_mut(&mut self) -> Result<&mut Mat> {
+		if !self.is_unique() {
+			self.inner = self.inner.try_clone()?;
+		}
+		Ok(&mut self.inner)
+	}
+
+	/// Returns the underlying `Mat`, the data is deep-copied first if it's shared with other `Mat` headers, same as
+	/// [SharedMat::make_mut]
+	pub fn into_mat(mut self) -> Result<Mat> {
+		self.make_mut()?;
+		Ok(self.inner)
+	}
+}
+
+impl Deref for SharedMat {
+	type Target = Mat;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		&self.inner
+	}
+}
+
+impl From<Mat> for SharedMat {
+	#[inline]
+	fn from(mat: Mat) -> Self {
+		Self::new(mat)
+	}
+}
+
+impl TryFrom<SharedMat> for Mat {
+	type Error = crate::Error;
+
+	#[inline]
+	fn try_from(s: SharedMat) -> Result<Self> {
+		s.into_mat()
+	}
+}
+
+impl Clone for SharedMat {
+	/// Creates a new `Mat` header referencing the same data, the data itself is not copied
+	#[inline]
+	fn clone(&self) -> Self {
+		self.try_clone().expect("Cannot clone SharedMat")
+	}
+}
+
+impl MatTraitConst for SharedMat {
+	#[inline]
+	fn as_raw_Mat(&self) -> *const c_void {
+		self.inner.as_raw_Mat()
+	}
+}
+
+impl ToInputArray for SharedMat {
+	#[inline]
+	fn input_array(&self) -> Result<BoxedRef<_InputArray>> {
+		self.inner.input_array()
+	}
+}
+
+impl fmt::Debug for SharedMat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(&self.inner, f)
+	}
+}
@@ -0,0 +1,468 @@
+use std::ffi::{c_void, CString};
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
+use std::sync::atomic::{fence, AtomicU64, Ordering};
+use std::{io, mem, ptr};
+
+use crate::boxed_ref::{BoxedRef, BoxedRefMut};
+use crate::core::{Mat, MatTraitConst, CV_MAX_DIM};
+use crate::{core, Error, Result};
+
+const MAGIC: [u8; 8] = *b"OCVRSSHM";
+const VERSION: u32 = 1;
+const MAX_DIMS: usize = CV_MAX_DIM as usize;
+/// Alignment of the slot data, same as `CV_MALLOC_ALIGN` used by the default OpenCV allocator
+const DATA_ALIGN: usize = 64;
+
+/// Header at the start of the shared memory segment, all fields except `sequence` are immutable after the creation
+///
+/// The layout is `#[repr(C)]` so that the segment can also be accessed from other languages:
+/// - `magic`: `b"OCVRSSHM"`, `version`: 1
+/// - `typ`: OpenCV type of the `Mat` elements, `dims`: number of the used entries in `sizes` and `steps`
+/// - `steps`: byte steps of the dimensions (the data is always continuous)
+/// - `slots`: number of the frames in the ring buffer, the frame `n` is stored in the slot `(n - 1) % slots`, the slot `i`
+///   starts at `data_offset + i * slot_size` bytes from the segment start
+/// - `sequence`: number of the published frames, the last published frame is `sequence`, 0 means there are no frames yet
+#[repr(C)]
+struct Header {
+	magic: [u8; 8],
+	version: u32,
+	typ: i32,
+	dims: u32,
+	slots: u32,
+	sizes: [i32; MAX_DIMS],
+	steps: [u64; MAX_DIMS],
+	slot_size: u64,
+	data_offset: u64,
+	sequence: AtomicU64,
+}
+
+fn os_error(action: &str) -> Error {
+	Error::new(
+		core::StsError,
+		format!("Can't {action} the shared memory segment: {}", io::Error::last_os_error()),
+	)
+}
+
+fn align_up(value: usize) -> Option<usize> {
+	value.checked_add(DATA_ALIGN - 1).map(|v| v / DATA_ALIGN * DATA_ALIGN)
+}
+
+fn segment_name(name: &str) -> Result<CString> {
+	let name = if name.starts_with('/') {
+		name.to_string()
+	} else {
+		format!("/{name}")
+	};
+	if name.len() < 2 || name[1..].contains('/') {
+		return Err(Error::new(
+			core::StsBadArg,
+			format!("Invalid shared memory segment name: {name}, it must not be empty or contain slashes"),
+		));
+	}
+	CString::new(name).map_err(|e| Error::new(core::StsBadArg, format!("Invalid shared memory segment name: {e}")))
+}
+
+/// Memory mapping of the whole shared memory segment
+struct Mapping {
+	ptr: *mut u8,
+	len: usize,
+}
+
+// the data is only accessed through the atomic sequence counter or via the `&mut` of the writer
+unsafe impl Send for Mapping {}
+
+unsafe impl Sync for Mapping {}
+
+impl Mapping {
+	fn new(fd: RawFd, len: usize, writable: bool) -> Result<Self> {
+		let prot = if writable {
+			libc::PROT_READ | libc::PROT_WRITE
+		} else {
+			libc::PROT_READ
+		};
+		let ptr = unsafe { libc::mmap(ptr::null_mut(), len, prot, libc::MAP_SHARED, fd, 0) };
+		if ptr == libc::MAP_FAILED {
+			return Err(os_error("map"));
+		}
+		Ok(Self { ptr: ptr.cast(), len })
+	}
+
+	#[inline]
+	fn header(&self) -> &Header {
+		// safe because the mapping is always bigger than the header and page-aligned
+		unsafe { &*self.ptr.cast::<Header>() }
+	}
+
+	#[inline]
+	fn sizes(&self) -> &[i32] {
+		let header = self.header();
+		&header.sizes[..header.dims as usize]
+	}
+
+	#[inline]
+	fn sequence(&self) -> u64 {
+		self.header().sequence.load(Ordering::Acquire)
+	}
+
+	/// Creates a `Mat` header over the slot data
+	///
+	/// # Safety
+	/// `slot` must be less than the slot count and the returned `Mat` must not outlive the mapping
+	unsafe fn slot_mat(&self, slot: u64) -> Result<Mat> {
+		let header = self.header();
+		// the offset is checked to be within the mapping on creation and on opening
+		let data = self.ptr.add((header.data_offset + slot * header.slot_size) as usize);
+		Mat::new_nd_with_data_unsafe_def(self.sizes(), header.typ, data.cast::<c_void>())
+	}
+}
+
+impl Drop for Mapping {
+	fn drop(&mut self) {
+		unsafe { libc::munmap(self.ptr.cast(), self.len) };
+	}
+}
+
+/// Writer side of a `Mat` ring buffer in the shared memory segment for the zero-copy frame exchange between processes
+///
+/// The segment is created either with a name using `shm_open` ([SharedMemoryMat::create]) or anonymously using `memfd_create`
+/// ([SharedMemoryMat::create_anonymous]), in the latter case the file descriptor must be passed to the other process (e.g. by
+/// inheriting it or via a Unix socket). The segment contains a header describing the dimensions, type and steps of the frames
+/// and `slots` frame buffers used in a round-robin fashion. The writer fills the next slot via [SharedMemoryMat::next_slot]
+/// and then makes it visible to the readers with [SharedMemoryMat::publish]. See [SharedMemoryMatReader] for the reader side.
+/// ```no_run
+/// # use opencv::core::{SharedMemoryMat, SharedMemoryMatReader, Vec3b};
+/// # use opencv::prelude::*;
+/// # use opencv::videoio;
+/// # fn main() -> opencv::Result<()> {
+/// // capture process
+/// let mut frames = SharedMemoryMat::create("camera0", &[480, 640], Vec3b::opencv_type(), 4)?;
+/// let mut capture = videoio::VideoCapture::new(0, videoio::CAP_ANY)?;
+/// while capture.read(&mut frames.next_slot()?)? {
+///     frames.publish();
+/// }
+///
+/// // processing process
+/// let frames = SharedMemoryMatReader::open("camera0")?;
+/// if let Some((sequence, frame)) = frames.latest()? {
+///     // process the copy of the frame
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct SharedMemoryMat {
+	mapping: Mapping,
+	fd: OwnedFd,
+	name: Option<CString>,
+}
+
+impl SharedMemoryMat {
+	/// Creates a new named shared memory segment with `slots` frames of the specified dimension sizes and type
+	///
+	/// The segment must not already exist, it's removed when the `SharedMemoryMat` is dropped, the readers that have already
+	/// opened it keep it mapped.
+	pub fn create(name: &str, sizes: &[i32], typ: i32, slots: usize) -> Result<Self> {
+		let name = segment_name(name)?;
+		let fd = unsafe { libc::shm_open(name.as_ptr(), libc::O_CREAT | libc::O_EXCL | libc::O_RDWR, 0o600) };
+		if fd < 0 {
+			return Err(os_error("create"));
+		}
+		// safe because the fd is newly opened and not owned by anything else
+		let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+		Self::init(fd, Some(name.clone()), sizes, typ, slots).map_err(|e| {
+			unsafe { libc::shm_unlink(name.as_ptr()) };
+			e
+		})
+	}
+
+	/// Creates a new anonymous shared memory segment with `slots` frames of the specified dimension sizes and type
+	///
+	/// Use [AsRawFd] or [AsFd] to get the file descriptor that must be passed to the reader process.
+	pub fn create_anonymous(sizes: &[i32], typ: i32, slots: usize) -> Result<Self> {
+		let fd = unsafe { libc::memfd_create(b"opencv-mat\0".as_ptr().cast(), libc::MFD_CLOEXEC) };
+		if fd < 0 {
+			return Err(os_error("create"));
+		}
+		// safe because the fd is newly created and not owned by anything else
+		let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+		Self::init(fd, None, sizes, typ, slots)
+	}
+
+	fn init(fd: OwnedFd, name: Option<CString>, sizes: &[i32], typ: i32, slots: usize) -> Result<Self> {
+		if sizes.is_empty() || sizes.len() > MAX_DIMS {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!(
+					"Number of dimensions must be between 1 and {MAX_DIMS}, but it's {}",
+					sizes.len()
+				),
+			));
+		}
+		if slots < 2 {
+			return Err(Error::new(core::StsBadArg, "Ring buffer must have at least 2 slots"));
+		}
+		let slots_u32 = u32::try_from(slots).map_err(|_| Error::new(core::StsOutOfRange, "Too many slots"))?;
+		let mut steps = [0; MAX_DIMS];
+		let mut step = core::get_elem_size(typ)?;
+		for (i, size) in sizes.iter().enumerate().rev() {
+			if *size <= 0 {
+				return Err(Error::new(core::StsOutOfRange, format!("Dimension {i} must be positive")));
+			}
+			steps[i] = step as u64;
+			step = step
+				.checked_mul(*size as usize)
+				.ok_or_else(|| Error::new(core::StsOutOfRange, "Frame size is too big"))?;
+		}
+		let too_big = || Error::new(core::StsOutOfRange, "Shared memory segment size is too big");
+		let data_offset = align_up(mem::size_of::<Header>()).ok_or_else(too_big)?;
+		let slot_size = align_up(step).ok_or_else(too_big)?;
+		let len = slot_size
+			.checked_mul(slots)
+			.and_then(|data_len| data_len.checked_add(data_offset))
+			.ok_or_else(too_big)?;
+		if unsafe { libc::ftruncate(fd.as_raw_fd(), libc::off_t::try_from(len).map_err(|_| too_big())?) } != 0 {
+			return Err(os_error("resize"));
+		}
+		let mapping = Mapping::new(fd.as_raw_fd(), len, true)?;
+		let mut header_sizes = [0; MAX_DIMS];
+		header_sizes[..sizes.len()].copy_from_slice(sizes);
+		// safe because the mapping is writable and not yet shared with the readers
+		unsafe {
+			mapping.ptr.cast::<Header>().write(Header {
+				magic: MAGIC,
+				version: VERSION,
+				typ,
+				dims: sizes.len() as u32,
+				slots: slots_u32,
+				sizes: header_sizes,
+				steps,
+				slot_size: slot_size as u64,
+				data_offset: data_offset as u64,
+				sequence: AtomicU64::new(0),
+			})
+		};
+		Ok(Self { mapping, fd, name })
+	}
+
+	/// Name of the segment, `None` for the anonymous segment
+	#[inline]
+	pub fn name(&self) -> Option<&str> {
+		self.name.as_ref().and_then(|name| name.to_str().ok())
+	}
+
+	/// Dimension sizes of the frames
+	#[inline]
+	pub fn sizes(&self) -> &[i32] {
+		self.mapping.sizes()
+	}
+
+	/// OpenCV type of the frames
+	#[inline]
+	pub fn typ(&self) -> i32 {
+		self.mapping.header().typ
+	}
+
+	/// Number of the frames in the ring buffer
+	#[inline]
+	pub fn slots(&self) -> usize {
+		self.mapping.header().slots as usize
+	}
+
+	/// Sequence number of the last published frame, 0 if there were no frames published yet
+	#[inline]
+	pub fn sequence(&self) -> u64 {
+		self.mapping.sequence()
+	}
+
+	/// Returns the `Mat` over the slot for the next frame, it contains the data of the frame `sequence() + 1 - slots()`
+	///
+	/// The `Mat` must not be reallocated (e.g. by passing it as an output array of a different size or type), otherwise the
+	/// new data is not written to the shared memory.
+	pub fn next_slot(&mut self) -> Result<BoxedRefMut<Mat>> {
+		let slot = self.sequence() % self.slots() as u64;
+		// safe because the slot index is within the bounds and the returned `Mat` borrows `self`
+		let mat = unsafe { self.mapping.slot_mat(slot) }?;
+		Ok(BoxedRefMut::from(mat))
+	}
+
+	/// Makes the frame written to the slot returned by [SharedMemoryMat::next_slot] available to the readers, returns its
+	/// sequence number
+	#[inline]
+	pub fn publish(&mut self) -> u64 {
+		self.mapping.header().sequence.fetch_add(1, Ordering::Release) + 1
+	}
+}
+
+impl Drop for SharedMemoryMat {
+	fn drop(&mut self) {
+		if let Some(name) = &self.name {
+			unsafe { libc::shm_unlink(name.as_ptr()) };
+		}
+	}
+}
+
+impl AsFd for SharedMemoryMat {
+	#[inline]
+	fn as_fd(&self) -> BorrowedFd {
+		self.fd.as_fd()
+	}
+}
+
+impl AsRawFd for SharedMemoryMat {
+	#[inline]
+	fn as_raw_fd(&self) -> RawFd {
+		self.fd.as_raw_fd()
+	}
+}
+
+/// Reader side of a `Mat` ring buffer in the shared memory segment created by [SharedMemoryMat]
+///
+/// The writer doesn't wait for the readers, so a frame is only guaranteed to be intact while it's one of the last `slots - 1`
+/// published frames (see [SharedMemoryMatReader::is_valid]). [SharedMemoryMatReader::frame] and
+/// [SharedMemoryMatReader::latest] return a copy of the frame that is checked to be intact, the zero-copy access to the
+/// mapped frame is available with the `unsafe` [SharedMemoryMatReader::frame_ref].
+pub struct SharedMemoryMatReader {
+	mapping: Mapping,
+}
+
+impl SharedMemoryMatReader {
+	/// Opens the named shared memory segment created by [SharedMemoryMat::create]
+	pub fn open(name: &str) -> Result<Self> {
+		let name = segment_name(name)?;
+		let fd = unsafe { libc::shm_open(name.as_ptr(), libc::O_RDONLY, 0) };
+		if fd < 0 {
+			return Err(os_error("open"));
+		}
+		// safe because the fd is newly opened and not owned by anything else
+		Self::from_fd(unsafe { OwnedFd::from_raw_fd(fd) })
+	}
+
+	/// Maps the shared memory segment passed as the file descriptor, e.g. the one created by
+	/// [SharedMemoryMat::create_anonymous]
+	pub fn from_fd(fd: OwnedFd) -> Result<Self> {
+		let invalid = |msg: &str| Error::new(core::StsBadArg, format!("Invalid shared memory segment: {msg}"));
+		let mut stat = mem::MaybeUninit::<libc::stat>::uninit();
+		if unsafe { libc::fstat(fd.as_raw_fd(), stat.as_mut_ptr()) } != 0 {
+			return Err(os_error("query"));
+		}
+		let len = usize::try_from(unsafe { stat.assume_init() }.st_size).map_err(|_| invalid("bad size"))?;
+		if len < mem::size_of::<Header>() {
+			return Err(invalid("too small"));
+		}
+		// the mapping stays valid after the fd is closed
+		let mapping = Mapping::new(fd.as_raw_fd(), len, false)?;
+		let header = mapping.header();
+		if header.magic != MAGIC {
+			return Err(invalid("bad magic"));
+		}
+		if header.version != VERSION {
+			return Err(invalid(&format!("unsupported version {}", header.version)));
+		}
+		if header.dims == 0 || header.dims as usize > MAX_DIMS || header.slots < 2 {
+			return Err(invalid("bad dimensions or slot count"));
+		}
+		let frame_size = mapping
+			.sizes()
+			.iter()
+			.try_fold(core::get_elem_size(header.typ)? as u64, |acc, size| {
+				u64::try_from(*size).ok().and_then(|size| acc.checked_mul(size))
+			});
+		let data_end = header
+			.slot_size
+			.checked_mul(u64::from(header.slots))
+			.and_then(|data_len| data_len.checked_add(header.data_offset));
+		match (frame_size, data_end) {
+			(Some(frame_size), Some(data_end))
+				if frame_size <= header.slot_size
+					&& header.data_offset >= mem::size_of::<Header>() as u64
+					&& data_end <= len as u64 => {}
+			_ => return Err(invalid("frame data doesn't fit")),
+		}
+		Ok(Self { mapping })
+	}
+
+	/// Dimension sizes of the frames
+	#[inline]
+	pub fn sizes(&self) -> &[i32] {
+		self.mapping.sizes()
+	}
+
+	/// Byte steps of the frame dimensions
+	#[inline]
+	pub fn steps(&self) -> &[u64] {
+		let header = self.mapping.header();
+		&header.steps[..header.dims as usize]
+	}
+
+	/// OpenCV type of the frames
+	#[inline]
+	pub fn typ(&self) -> i32 {
+		self.mapping.header().typ
+	}
+
+	/// Number of the frames in the ring buffer
+	#[inline]
+	pub fn slots(&self) -> usize {
+		self.mapping.header().slots as usize
+	}
+
+	/// Sequence number of the last published frame, 0 if there were no frames published yet
+	#[inline]
+	pub fn sequence(&self) -> u64 {
+		self.mapping.sequence()
+	}
+
+	/// Returns `true` if the frame with the specified sequence number is published and is not being overwritten by the writer
+	#[inline]
+	pub fn is_valid(&self, sequence: u64) -> bool {
+		let last = self.sequence();
+		sequence > 0 && sequence <= last && last - sequence < self.slots() as u64 - 1
+	}
+
+	fn check_valid(&self, sequence: u64) -> Result<()> {
+		if self.is_valid(sequence) {
+			Ok(())
+		} else {
+			Err(Error::new(
+				core::StsOutOfRange,
+				format!("Frame {sequence} is not available, last published frame: {}", self.sequence()),
+			))
+		}
+	}
+
+	/// Returns a copy of the frame with the specified sequence number, it fails if the frame is not valid (see
+	/// [SharedMemoryMatReader::is_valid]) or if the writer started overwriting it during the copying
+	pub fn frame(&self, sequence: u64) -> Result<Mat> {
+		// safe because the mapped frame is dropped right after the copying and the copy is discarded if the frame was
+		// overwritten in the meantime
+		let out = unsafe { self.frame_ref(sequence) }?.try_clone()?;
+		// order the copying before the sequence check, the same way as in a seqlock
+		fence(Ordering::Acquire);
+		self.check_valid(sequence)?;
+		Ok(out)
+	}
+
+	/// Returns the mapped frame with the specified sequence number without copying, it fails if the frame is not valid (see
+	/// [SharedMemoryMatReader::is_valid])
+	///
+	/// # Safety
+	/// The writer doesn't wait for the readers, so the caller must make sure that the writer publishes less than `slots - 1`
+	/// new frames while the returned `Mat` is alive, otherwise its data is modified concurrently with the reading.
+	pub unsafe fn frame_ref(&self, sequence: u64) -> Result<BoxedRef<Mat>> {
+		self.check_valid(sequence)?;
+		let slot = (sequence - 1) % self.slots() as u64;
+		// the slot index is within the bounds and the returned `Mat` borrows `self`, the data is not modified through it
+		// because it's only accessible as `&Mat`
+		let mat = self.mapping.slot_mat(slot)?;
+		Ok(BoxedRef::from(mat))
+	}
+
+	/// Returns a copy of the last published frame together with its sequence number, `None` if there were no frames published
+	/// yet, see [SharedMemoryMatReader::frame]
+	pub fn latest(&self) -> Result<Option<(u64, Mat)>> {
+		let sequence = self.sequence();
+		if sequence == 0 {
+			return Ok(None);
+		}
+		self.frame(sequence).map(|frame| Some((sequence, frame)))
+	}
+}
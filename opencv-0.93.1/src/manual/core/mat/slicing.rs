@@ -0,0 +1,121 @@
+use std::ops::{Bound, RangeBounds};
+
+use crate::core::{Range, Vector};
+use crate::{core, Error, Result};
+
+/// Ranges that can be used to take a slice of a `Mat`, see [MatTraitConstManual::slice](crate::core::MatTraitConstManual::slice)
+///
+/// Implemented for tuples (up to 8 elements), arrays and slices of Rust ranges, e.g. `(.., 10..20)`, `(1..=3, .., 5..)` or
+/// `[0..2, 0..2]`. Every element corresponds to a single `Mat` dimension, dimensions that are not covered by the ranges are
+/// taken in full.
+pub trait MatSliceRanges {
+	/// Bounds of the range for every dimension starting from the first one
+	fn to_bounds(&self) -> Vec<(Bound<i32>, Bound<i32>)>;
+}
+
+#[inline]
+fn to_bound(range: &impl RangeBounds<i32>) -> (Bound<i32>, Bound<i32>) {
+	(range.start_bound().cloned(), range.end_bound().cloned())
+}
+
+macro_rules! mat_slice_ranges_tuple {
+	($($typ: ident => $idx: tt),+ $(,)?) => {
+		impl<$($typ: RangeBounds<i32>),+> MatSliceRanges for ($($typ,)+) {
+			#[inline]
+			fn to_bounds(&self) -> Vec<(Bound<i32>, Bound<i32>)> {
+				vec![$(to_bound(&self.$idx)),+]
+			}
+		}
+	};
+}
+
+mat_slice_ranges_tuple!(R0 => 0);
+mat_slice_ranges_tuple!(R0 => 0, R1 => 1);
+mat_slice_ranges_tuple!(R0 => 0, R1 => 1, R2 => 2);
+mat_slice_ranges_tuple!(R0 => 0, R1 => 1, R2 => 2, R3 => 3);
+mat_slice_ranges_tuple!(R0 => 0, R1 => 1, R2 => 2, R3 => 3, R4 => 4);
+mat_slice_ranges_tuple!(R0 => 0, R1 => 1, R2 => 2, R3 => 3, R4 => 4, R5 => 5);
+mat_slice_ranges_tuple!(R0 => 0, R1 => 1, R2 => 2, R3 => 3, R4 => 4, R5 => 5, R6 => 6);
+mat_slice_ranges_tuple!(R0 => 0, R1 => 1, R2 => 2, R3 => 3, R4 => 4, R5 => 5, R6 => 6, R7 => 7);
+
+impl<R: RangeBounds<i32>, const N: usize> MatSliceRanges for [R; N] {
+	#[inline]
+	fn to_bounds(&self) -> Vec<(Bound<i32>, Bound<i32>)> {
+		self.iter().map(to_bound).collect()
+	}
+}
+
+impl<R: RangeBounds<i32>> MatSliceRanges for &[R] {
+	#[inline]
+	fn to_bounds(&self) -> Vec<(Bound<i32>, Bound<i32>)> {
+		self.iter().map(to_bound).collect()
+	}
+}
+
+/// Converts the Rust range bounds into OpenCV ranges checking them against the `Mat` dimensions
+pub(crate) fn bounds_to_ranges(bounds: &[(Bound<i32>, Bound<i32>)], sizes: &[i32]) -> Result<Vector<Range>> {
+	if bounds.len() > sizes.len() {
+		return Err(Error::new(
+			core::StsUnmatchedSizes,
+			format!(
+				"Amount of Mat dimensions: {} is less than the amount of requested ranges: {}",
+				sizes.len(),
+				bounds.len()
+			),
+		));
+	}
+	let mut out = Vector::with_capacity(sizes.len());
+	for (dim, &size) in sizes.iter().enumerate() {
+		let (start, end) = bounds.get(dim).cloned().unwrap_or((Bound::Unbounded, Bound::Unbounded));
+		let start = match start {
+			Bound::Included(start) => Some(start),
+			Bound::Excluded(start) => start.checked_add(1),
+			Bound::Unbounded => Some(0),
+		};
+		let end = match end {
+			Bound::Included(end) => end.checked_add(1),
+			Bound::Excluded(end) => Some(end),
+			Bound::Unbounded => Some(size),
+		};
+		let range = match (start, end) {
+			(Some(0), Some(end)) if end == size => Range::all()?,
+			(Some(start), Some(end)) if 0 <= start && start < end && end <= size => Range::new(start, end)?,
+			_ => {
+				return Err(Error::new(
+					core::StsOutOfRange,
+					format!("Range along dimension: {dim} is empty or out of bounds 0..{size}"),
+				))
+			}
+		};
+		out.push(range);
+	}
+	Ok(out)
+}
+
+/// Creates a pair of ranges that split the `Mat` into 2 non-overlapping parts along dimension `dim` at index `mid`
+pub(crate) fn split_ranges(dim: i32, mid: i32, sizes: &[i32]) -> Result<(Vector<Range>, Vector<Range>)> {
+	let size = usize::try_from(dim).ok().and_then(|dim| sizes.get(dim)).copied().ok_or_else(|| {
+		Error::new(
+			core::StsOutOfRange,
+			format!("Dimension: {dim} out of bounds 0..{}", sizes.len()),
+		)
+	})?;
+	if mid <= 0 || mid >= size {
+		return Err(Error::new(
+			core::StsOutOfRange,
+			format!("Split index: {mid} must be within 1..{size} so that both parts are not empty"),
+		));
+	}
+	let mut first = Vector::with_capacity(sizes.len());
+	let mut second = Vector::with_capacity(sizes.len());
+	for i in 0..sizes.len() {
+		if i == dim as usize {
+			first.push(Range::new(0, mid)?);
+			second.push(Range::new(mid, size)?);
+		} else {
+			first.push(Range::all()?);
+			second.push(Range::all()?);
+		}
+	}
+	Ok((first, second))
+}
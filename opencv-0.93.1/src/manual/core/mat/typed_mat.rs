@@ -0,0 +1,230 @@
+use std::convert::TryFrom;
+use std::ffi::c_void;
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::boxed_ref::{BoxedRef, BoxedRefMut};
+use crate::core::{
+	Mat, MatTrait, MatTraitConst, MatTraitConstManual, MatTraitManual, Mat_, Scalar, ToInputArray, ToInputOutputArray,
+	ToOutputArray, _InputArray, _InputOutputArray, _OutputArray,
+};
+use crate::{core, Error, Result};
+
+use super::{match_format, DataType};
+
+/// `Mat` with the element type and the number of dimensions known at compile time
+///
+/// The element type `T` defines both the depth and the channel count of the `Mat` (e.g. `TypedMat<Vec3b, 2>` is a 2-dimensional
+/// `CV_8UC3` image) and the shape is exposed as `[usize; DIMS]`. Conversion from `Mat` and `Mat_` checks both, after that
+/// the element access doesn't need a type parameter. `DIMS` must be at least 2 because that's the minimum number of
+/// dimensions of an OpenCV `Mat`.
+///
+/// When passed to OpenCV functions as an output array it's marked as having a fixed type, so a function that tries to
+/// produce the output of a different type returns an error instead of silently changing the element type. OpenCV doesn't
+/// have a way to fix the number of dimensions of the output, so only the 2-dimensional `TypedMat` can be used as an output
+/// array, the same as `Mat_<T>` in C++:
+/// ```no_run
+/// # use opencv::core::{TypedMat, Vec3b};
+/// # use opencv::imgproc;
+/// # fn main() -> opencv::Result<()> {
+/// let bgr = TypedMat::<Vec3b, 2>::new([480, 640])?;
+/// let mut gray = TypedMat::<u8, 2>::new([0, 0])?;
+/// imgproc::cvt_color_def(&bgr, &mut gray, imgproc::COLOR_BGR2GRAY)?;
+/// assert_eq!([480, 640], gray.shape());
+/// # Ok(())
+/// # }
+/// ```
+pub struct TypedMat<T, const DIMS: usize> {
+	inner: Mat,
+	_type: PhantomData<T>,
+}
+
+impl<T: DataType, const DIMS: usize> TypedMat<T, DIMS> {
+	const DIMS_CHECK: () = assert!(DIMS >= 2, "TypedMat must have at least 2 dimensions");
+
+	/// Creates a new zero-filled `TypedMat` of the specified shape
+	pub fn new(shape: [usize; DIMS]) -> Result<Self> {
+		#[allow(clippy::let_unit_value)]
+		let _ = Self::DIMS_CHECK;
+		let sizes = shape
+			.iter()
+			.map(|&size| {
+				i32::try_from(size).map_err(|_| Error::new(core::StsOutOfRange, format!("Dimension size: {size} is too high")))
+			})
+			.collect::<Result<Vec<_>>>()?;
+		Ok(Self {
+			inner: Mat::new_nd_with_default(&sizes, T::opencv_type(), Scalar::all(0.))?,
+			_type: PhantomData,
+		})
+	}
+
+	/// Creates a new `TypedMat` of the specified shape copying the elements from `data` in row-major order
+	pub fn from_slice(shape: [usize; DIMS], data: &[T]) -> Result<Self> {
+		let mut out = Self::new(shape)?;
+		let dst = out.data_typed_mut()?;
+		if dst.len() != data.len() {
+			return Err(Error::new(
+				core::StsUnmatchedSizes,
+				format!(
+					"The length of the slice: {} must be: {} to match the passed shape: {shape:?}",
+					data.len(),
+					dst.len()
+				),
+			));
+		}
+		dst.copy_from_slice(data);
+		Ok(out)
+	}
+
+	/// Size of every dimension, all zeroes for an empty `TypedMat`
+	#[inline]
+	pub fn shape(&self) -> [usize; DIMS] {
+		let mut out = [0; DIMS];
+		for (dst, &size) in out.iter_mut().zip(self.inner.mat_size().iter()) {
+			// safe because Mat dimensions can't be negative
+			*dst = size as usize;
+		}
+		out
+	}
+
+	/// Returns the element at the specified index, index is checked against the `Mat` dimensions
+	#[inline]
+	pub fn at(&self, idx: [usize; DIMS]) -> Result<&T> {
+		self.inner.at_nd(&idx_to_i32(idx)?)
+	}
+
+	/// Returns the mutable reference to the element at the specified index, see [TypedMat::at]
+	#[inline]
+	pub fn at_mut(&mut self, idx: [usize; DIMS]) -> Result<&mut T> {
+		self.inner.at_nd_mut(&idx_to_i32(idx)?)
+	}
+
+	/// Returns all elements as a slice, `Mat` must be continuous
+	#[inline]
+	pub fn data_typed(&self) -> Result<&[T]> {
+		self.inner.data_typed()
+	}
+
+	/// Returns all elements as a mutable slice, `Mat` must be continuous
+	#[inline]
+	pub fn data_typed_mut(&mut self) -> Result<&mut [T]> {
+		self.inner.data_typed_mut()
+	}
+
+	#[inline]
+	pub fn as_untyped(&self) -> &Mat {
+		&self.inner
+	}
+
+	#[inline]
+	pub fn into_untyped(self) -> Mat {
+		self.inner
+	}
+}
+
+#[inline]
+fn idx_to_i32<const DIMS: usize>(idx: [usize; DIMS]) -> Result<[i32; DIMS]> {
+	let mut out = [0; DIMS];
+	for (dst, i) in out.iter_mut().zip(idx) {
+		*dst = i32::try_from(i).map_err(|_| Error::new(core::StsOutOfRange, format!("Index: {i} is too high")))?;
+	}
+	Ok(out)
+}
+
+impl<T: DataType, const DIMS: usize> TryFrom<Mat> for TypedMat<T, DIMS> {
+	type Error = Error;
+
+	/// Checks that the element type and the number of dimensions of the `Mat` match the ones of `TypedMat`
+	fn try_from(mat: Mat) -> Result<Self, Self::Error> {
+		#[allow(clippy::let_unit_value)]
+		let _ = Self::DIMS_CHECK;
+		match_format::<T>(mat.typ())?;
+		let dims = mat.dims();
+		// safe because the number of dimensions can't be negative
+		if dims as usize != DIMS {
+			return Err(Error::new(
+				core::StsUnmatchedSizes,
+				format!("Mat must have {DIMS} dimensions, but it has: {dims}"),
+			));
+		}
+		Ok(Self {
+			inner: mat,
+			_type: PhantomData,
+		})
+	}
+}
+
+impl<T: DataType, const DIMS: usize> TryFrom<Mat_<T>> for TypedMat<T, DIMS> {
+	type Error = Error;
+
+	#[inline]
+	fn try_from(mat: Mat_<T>) -> Result<Self, Self::Error> {
+		Self::try_from(mat.into_untyped())
+	}
+}
+
+impl<T: DataType, const DIMS: usize> From<TypedMat<T, DIMS>> for Mat {
+	#[inline]
+	fn from(s: TypedMat<T, DIMS>) -> Self {
+		s.inner
+	}
+}
+
+impl<T: DataType, const DIMS: usize> From<TypedMat<T, DIMS>> for Mat_<T> {
+	#[inline]
+	fn from(s: TypedMat<T, DIMS>) -> Self {
+		s.inner.try_into_typed().expect("TypedMat element type is always correct")
+	}
+}
+
+impl<T: DataType, const DIMS: usize> Clone for TypedMat<T, DIMS> {
+	#[inline]
+	fn clone(&self) -> Self {
+		Self {
+			inner: self.inner.clone(),
+			_type: PhantomData,
+		}
+	}
+}
+
+impl<T, const DIMS: usize> MatTraitConst for TypedMat<T, DIMS> {
+	#[inline]
+	fn as_raw_Mat(&self) -> *const c_void {
+		self.inner.as_raw_Mat()
+	}
+}
+
+impl<T, const DIMS: usize> ToInputArray for TypedMat<T, DIMS> {
+	#[inline]
+	fn input_array(&self) -> Result<BoxedRef<_InputArray>> {
+		self.inner.input_array()
+	}
+}
+
+impl<T: DataType> ToOutputArray for TypedMat<T, 2> {
+	/// Same as `_OutputArray(Mat_<T>&)` in C++, OpenCV fails to create the output of a type that differs from `T`
+	#[inline]
+	fn output_array(&mut self) -> Result<BoxedRefMut<_OutputArray>> {
+		let flags = core::_InputArray_FIXED_TYPE | core::_InputArray_MAT | T::opencv_type();
+		// safe because the flags match the object which is a Mat that outlives the returned array
+		let out = unsafe { _OutputArray::new(flags, self.inner.as_raw_mut_Mat()) }?;
+		Ok(BoxedRefMut::from(out))
+	}
+}
+
+impl<T: DataType> ToInputOutputArray for TypedMat<T, 2> {
+	/// Same as `_InputOutputArray(Mat_<T>&)` in C++, see [TypedMat::output_array]
+	#[inline]
+	fn input_output_array(&mut self) -> Result<BoxedRefMut<_InputOutputArray>> {
+		let flags = core::_InputArray_FIXED_TYPE | core::_InputArray_MAT | T::opencv_type();
+		// safe because the flags match the object which is a Mat that outlives the returned array
+		let out = unsafe { _InputOutputArray::new(flags, self.inner.as_raw_mut_Mat()) }?;
+		Ok(BoxedRefMut::from(out))
+	}
+}
+
+impl<T, const DIMS: usize> fmt::Debug for TypedMat<T, DIMS> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(&self.inner, f)
+	}
+}
@@ -26,6 +26,7 @@ fn partial_max<T: PartialOrd>(a: T, b: T) -> T {
 /// [docs.opencv.org](https://docs.opencv.org/master/d2/d44/classcv_1_1Rect__.html)
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rect_<T> {
 	pub x: T,
 	pub y: T,
@@ -0,0 +1,435 @@
+//! `serde` support for the core value types and `Mat`
+//!
+//! `Point_`, `Point3_`, `Size_` and `Rect_` derive the implementations directly, they are serialized as structs with
+//! the same field names as in OpenCV. `VecN` (and hence `Scalar_`) and `Matx` are serialized as flat tuples of their
+//! elements, `Matx` elements go in row-major order.
+//!
+//! `Mat` uses the same layout as `opencv-matrix` (or `opencv-nd-matrix` for more than 2 dimensions) nodes produced by
+//! `FileStorage`, so e.g. a JSON produced by `serde_json` can be read back by `FileStorage` and vice versa:
+//! ```json
+//! { "type_id": "opencv-matrix", "rows": 2, "cols": 2, "dt": "3u", "data": [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12] }
+//! ```
+//! When deserializing from a self-describing format `dt` must come before `data`.
+
+use std::marker::PhantomData;
+use std::{fmt, mem, slice};
+
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, SerializeStruct, SerializeTuple};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::core;
+use crate::core::{
+	DMatch, KeyPoint, KeyPointTraitConst, Mat, MatTraitConst, MatTraitConstManual, MatTraitManual, Matx, Point2f, RotatedRect,
+	Scalar, Size2f, SizedArray, VecN,
+};
+
+const MATRIX_TYPE_ID: &str = "opencv-matrix";
+const ND_MATRIX_TYPE_ID: &str = "opencv-nd-matrix";
+const MAT_FIELDS: &[&str] = &["type_id", "rows", "cols", "sizes", "dt", "data"];
+
+/// Deserializes exactly `N` elements from a sequence
+struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for ArrayVisitor<T, N> {
+	type Value = [T; N];
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "a sequence of {N} elements")
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+		let mut out = Vec::with_capacity(N);
+		while out.len() < N {
+			let elem = seq
+				.next_element()?
+				.ok_or_else(|| de::Error::invalid_length(out.len(), &self))?;
+			out.push(elem);
+		}
+		// can't fail because the Vec has exactly N elements
+		Ok(out.try_into().unwrap_or_else(|_| unreachable!()))
+	}
+}
+
+impl<T: Serialize, const N: usize> Serialize for VecN<T, N> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut tuple = serializer.serialize_tuple(N)?;
+		for elem in &self.0 {
+			tuple.serialize_element(elem)?;
+		}
+		tuple.end()
+	}
+}
+
+impl<'de, T: Deserialize<'de>, const N: usize> Deserialize<'de> for VecN<T, N> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer
+			.deserialize_tuple(N, ArrayVisitor::<T, N>(PhantomData))
+			.map(Self::from_array)
+	}
+}
+
+impl<T: Serialize, A: SizedArray<T>> Serialize for Matx<T, A> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let val: &[T] = self.val.as_ref();
+		let mut tuple = serializer.serialize_tuple(val.len())?;
+		for elem in val {
+			tuple.serialize_element(elem)?;
+		}
+		tuple.end()
+	}
+}
+
+struct MatxVisitor<T, A>(PhantomData<(T, A)>);
+
+impl<'de, T: Deserialize<'de> + Copy, A: SizedArray<T>> Visitor<'de> for MatxVisitor<T, A> {
+	type Value = Matx<T, A>;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "a sequence of {} elements", A::ROWS * A::COLS)
+	}
+
+	fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+		let first = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+		let mut out = A::all(first);
+		let val: &mut [T] = out.as_mut();
+		for (i, dst) in val.iter_mut().enumerate().skip(1) {
+			*dst = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+		}
+		Ok(Matx::from_array(out))
+	}
+}
+
+impl<'de, T: Deserialize<'de> + Copy, A: SizedArray<T>> Deserialize<'de> for Matx<T, A> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_tuple(A::ROWS * A::COLS, MatxVisitor(PhantomData))
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "DMatch", rename = "DMatch")]
+struct DMatchDef {
+	query_idx: i32,
+	train_idx: i32,
+	img_idx: i32,
+	distance: f32,
+}
+
+impl Serialize for DMatch {
+	#[inline]
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		DMatchDef::serialize(self, serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for DMatch {
+	#[inline]
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		DMatchDef::deserialize(deserializer)
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "RotatedRect", rename = "RotatedRect")]
+struct RotatedRectDef {
+	center: Point2f,
+	size: Size2f,
+	angle: f32,
+}
+
+impl Serialize for RotatedRect {
+	#[inline]
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		RotatedRectDef::serialize(self, serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for RotatedRect {
+	#[inline]
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		RotatedRectDef::deserialize(deserializer)
+	}
+}
+
+/// `KeyPoint` is not a simple type so it's converted through this struct
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "KeyPoint")]
+struct KeyPointDef {
+	pt: Point2f,
+	size: f32,
+	angle: f32,
+	response: f32,
+	octave: i32,
+	class_id: i32,
+}
+
+impl Serialize for KeyPoint {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		KeyPointDef {
+			pt: self.pt(),
+			size: self.size(),
+			angle: self.angle(),
+			response: self.response(),
+			octave: self.octave(),
+			class_id: self.class_id(),
+		}
+		.serialize(serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for KeyPoint {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let kp = KeyPointDef::deserialize(deserializer)?;
+		KeyPoint::new_point(kp.pt, kp.size, kp.angle, kp.response, kp.octave, kp.class_id).map_err(de::Error::custom)
+	}
+}
+
+/// Converts `Mat` depth and channel count to the `dt` string used by `FileStorage`, e.g. `CV_8UC3` becomes `"3u"`
+fn type_to_dt(depth: i32, channels: i32) -> Option<String> {
+	let depth = match depth {
+		core::CV_8U => 'u',
+		core::CV_8S => 'c',
+		core::CV_16U => 'w',
+		core::CV_16S => 's',
+		core::CV_32S => 'i',
+		core::CV_32F => 'f',
+		core::CV_64F => 'd',
+		_ => return None,
+	};
+	Some(if channels == 1 {
+		depth.to_string()
+	} else {
+		format!("{channels}{depth}")
+	})
+}
+
+/// Converts the `dt` string used by `FileStorage` to `Mat` type, see [type_to_dt]
+fn dt_to_type(dt: &str) -> Option<i32> {
+	let depth = match dt.chars().last()? {
+		'u' => core::CV_8U,
+		'c' => core::CV_8S,
+		'w' => core::CV_16U,
+		's' => core::CV_16S,
+		'i' => core::CV_32S,
+		'f' => core::CV_32F,
+		'd' => core::CV_64F,
+		_ => return None,
+	};
+	let channels = &dt[..dt.len() - 1];
+	let channels = if channels.is_empty() {
+		1
+	} else {
+		channels.parse().ok()?
+	};
+	(1..=core::CV_CN_MAX)
+		.contains(&channels)
+		.then(|| core::CV_MAKETYPE(depth, channels))
+}
+
+/// # Safety
+/// `T` must match the depth of the `Mat` that owns the `data`, we rely on OpenCV to make sure that it's correctly aligned
+#[inline]
+unsafe fn data_as<T>(data: &[u8]) -> &[T] {
+	if data.is_empty() {
+		return &[];
+	}
+	slice::from_raw_parts(data.as_ptr().cast::<T>(), data.len() / mem::size_of::<T>())
+}
+
+/// # Safety
+/// See [data_as]
+#[inline]
+unsafe fn data_as_mut<T>(data: &mut [u8]) -> &mut [T] {
+	if data.is_empty() {
+		return &mut [];
+	}
+	slice::from_raw_parts_mut(data.as_mut_ptr().cast::<T>(), data.len() / mem::size_of::<T>())
+}
+
+/// Calls `$func::<T, _>($args)` with `T` being the primitive type that matches the `$depth`
+macro_rules! with_depth_type {
+	($depth: expr, $func: ident($($args: expr),*), $unsupported: expr) => {
+		match $depth {
+			core::CV_8U => $func::<u8, _>($($args),*),
+			core::CV_8S => $func::<i8, _>($($args),*),
+			core::CV_16U => $func::<u16, _>($($args),*),
+			core::CV_16S => $func::<i16, _>($($args),*),
+			core::CV_32S => $func::<i32, _>($($args),*),
+			core::CV_32F => $func::<f32, _>($($args),*),
+			core::CV_64F => $func::<f64, _>($($args),*),
+			_ => $unsupported,
+		}
+	};
+}
+
+impl Serialize for Mat {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		fn serialize_data<T: Serialize, S: SerializeStruct>(state: &mut S, data: &[u8]) -> Result<(), S::Error> {
+			// safe because the type is selected from the Mat depth
+			state.serialize_field("data", unsafe { data_as::<T>(data) })
+		}
+
+		let depth = self.depth();
+		let dt = type_to_dt(depth, self.channels())
+			.ok_or_else(|| ser::Error::custom(format!("Mat with depth: {depth} can't be serialized")))?;
+		let continuous;
+		let mat = if self.is_continuous() {
+			self
+		} else {
+			continuous = self.try_clone().map_err(ser::Error::custom)?;
+			&continuous
+		};
+		let mut state = if mat.dims() <= 2 {
+			let mut state = serializer.serialize_struct("Mat", 5)?;
+			state.serialize_field("type_id", MATRIX_TYPE_ID)?;
+			state.serialize_field("rows", &mat.rows())?;
+			state.serialize_field("cols", &mat.cols())?;
+			state
+		} else {
+			let mut state = serializer.serialize_struct("Mat", 4)?;
+			state.serialize_field("type_id", ND_MATRIX_TYPE_ID)?;
+			state.serialize_field("sizes", &*mat.mat_size())?;
+			state
+		};
+		state.serialize_field("dt", &dt)?;
+		let data = mat.data_bytes().map_err(ser::Error::custom)?;
+		with_depth_type!(
+			depth,
+			serialize_data(&mut state, data),
+			unreachable!("Depth is checked by type_to_dt")
+		)?;
+		state.end()
+	}
+}
+
+/// Creates the `Mat` of the given `sizes` and `typ` from a sequence of its elements
+///
+/// The elements are collected before the `Mat` is allocated and their count is checked against the `sizes`, so untrusted
+/// `sizes` can't make us allocate more memory than the input itself takes.
+struct MatDataSeed<'s> {
+	sizes: &'s [i32],
+	typ: i32,
+}
+
+impl<'de> DeserializeSeed<'de> for MatDataSeed<'_> {
+	type Value = Mat;
+
+	fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		fn deserialize_data<'de, T: Deserialize<'de> + Copy, D: Deserializer<'de>>(
+			deserializer: D,
+			sizes: &[i32],
+			typ: i32,
+		) -> Result<Mat, D::Error> {
+			let data = Vec::<T>::deserialize(deserializer)?;
+			let channels = ((typ & core::CV_MAT_CN_MASK) >> core::CV_CN_SHIFT) + 1;
+			let len = sizes
+				.iter()
+				.chain([&channels])
+				.try_fold(1usize, |len, &size| len.checked_mul(usize::try_from(size).ok()?))
+				.ok_or_else(|| de::Error::invalid_value(de::Unexpected::Seq, &"non-negative Mat sizes"))?;
+			if data.len() != len {
+				return Err(de::Error::invalid_length(data.len(), &format!("{len} Mat elements").as_str()));
+			}
+			let mut out = Mat::new_nd_with_default(sizes, typ, Scalar::all(0.)).map_err(de::Error::custom)?;
+			let dst = out.data_bytes_mut().map_err(de::Error::custom)?;
+			// safe because the type is selected from the Mat depth
+			unsafe { data_as_mut::<T>(dst) }.copy_from_slice(&data);
+			Ok(out)
+		}
+
+		let depth = self.typ & core::CV_MAT_DEPTH_MASK;
+		with_depth_type!(
+			depth,
+			deserialize_data(deserializer, self.sizes, self.typ),
+			Err(de::Error::custom(format!("Mat with depth: {depth} can't be deserialized")))
+		)
+	}
+}
+
+fn mat_type<E: de::Error>(dt: &str) -> Result<i32, E> {
+	dt_to_type(dt).ok_or_else(|| E::invalid_value(de::Unexpected::Str(dt), &"Mat element type like u or 3f"))
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum MatField {
+	TypeId,
+	Rows,
+	Cols,
+	Sizes,
+	Dt,
+	Data,
+}
+
+struct MatVisitor;
+
+impl<'de> Visitor<'de> for MatVisitor {
+	type Value = Mat;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("an opencv-matrix or opencv-nd-matrix")
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+		fn next<'de, T: Deserialize<'de>, A: SeqAccess<'de>>(seq: &mut A, i: &mut usize) -> Result<T, A::Error> {
+			let out = seq
+				.next_element()?
+				.ok_or_else(|| de::Error::invalid_length(*i, &MatVisitor))?;
+			*i += 1;
+			Ok(out)
+		}
+
+		let mut i = 0;
+		let type_id: String = next(&mut seq, &mut i)?;
+		let sizes = match type_id.as_str() {
+			MATRIX_TYPE_ID => vec![next(&mut seq, &mut i)?, next(&mut seq, &mut i)?],
+			ND_MATRIX_TYPE_ID => next(&mut seq, &mut i)?,
+			_ => return Err(de::Error::unknown_variant(&type_id, &[MATRIX_TYPE_ID, ND_MATRIX_TYPE_ID])),
+		};
+		let dt: String = next(&mut seq, &mut i)?;
+		let typ = mat_type(&dt)?;
+		seq.next_element_seed(MatDataSeed { sizes: &sizes, typ })?
+			.ok_or_else(|| de::Error::invalid_length(i, &self))
+	}
+
+	fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+		let mut type_id = None;
+		let mut rows = None;
+		let mut cols = None;
+		let mut sizes = None;
+		let mut dt = None;
+		let mut out = None;
+		while let Some(field) = map.next_key()? {
+			match field {
+				MatField::TypeId => type_id = Some(map.next_value::<String>()?),
+				MatField::Rows => rows = Some(map.next_value::<i32>()?),
+				MatField::Cols => cols = Some(map.next_value::<i32>()?),
+				MatField::Sizes => sizes = Some(map.next_value::<Vec<i32>>()?),
+				MatField::Dt => dt = Some(map.next_value::<String>()?),
+				MatField::Data => {
+					let sizes = match (type_id.as_deref(), sizes.take(), rows, cols) {
+						(Some(MATRIX_TYPE_ID), _, Some(rows), Some(cols)) => vec![rows, cols],
+						(Some(ND_MATRIX_TYPE_ID), Some(sizes), _, _) => sizes,
+						(Some(MATRIX_TYPE_ID), ..) => return Err(de::Error::missing_field("rows and cols")),
+						(Some(ND_MATRIX_TYPE_ID), ..) => return Err(de::Error::missing_field("sizes")),
+						(Some(type_id), ..) => {
+							return Err(de::Error::unknown_variant(type_id, &[MATRIX_TYPE_ID, ND_MATRIX_TYPE_ID]))
+						}
+						(None, ..) => return Err(de::Error::missing_field("type_id")),
+					};
+					let dt = dt.as_deref().ok_or_else(|| de::Error::missing_field("dt"))?;
+					let typ = mat_type(dt)?;
+					out = Some(map.next_value_seed(MatDataSeed { sizes: &sizes, typ })?);
+				}
+			}
+		}
+		out.ok_or_else(|| de::Error::missing_field("data"))
+	}
+}
+
+impl<'de> Deserialize<'de> for Mat {
+	#[inline]
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_struct("Mat", MAT_FIELDS, MatVisitor)
+	}
+}
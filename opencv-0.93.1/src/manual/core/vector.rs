@@ -1,11 +1,15 @@
 use std::borrow::Borrow;
+use std::cmp::Ordering;
 use std::ffi::c_void;
 use std::iter::FromIterator;
 use std::marker::PhantomData;
 use std::mem::ManuallyDrop;
+use std::ops::{Bound, Index, IndexMut, RangeBounds};
+use std::slice::SliceIndex;
 use std::{fmt, mem, slice};
 
 pub use iter::{VectorIterator, VectorRefIterator};
+pub use slice_view::VectorSliceView;
 pub use vector_extern::{VectorExtern, VectorExternCopyNonBool};
 
 use crate::boxed_ref::BoxedRef;
@@ -13,7 +17,10 @@ use crate::platform_types::size_t;
 use crate::traits::{Boxed, OpenCVFromExtern, OpenCVIntoExternContainer, OpenCVType, OpenCVTypeExternContainer};
 use crate::Result;
 
+#[cfg(all(feature = "half", not(ocvrs_opencv_branch_32)))]
+mod half;
 mod iter;
+mod slice_view;
 mod vector_extern;
 
 /// Wrapper for C++ [std::vector](https://en.cppreference.com/w/cpp/container/vector)
@@ -210,6 +217,192 @@ where
 		T::opencv_from_extern(val)
 	}
 
+	/// Get the first element, `None` if the Vector is empty
+	#[inline]
+	pub fn first(&self) -> Option<T>
+	where
+		T: OpenCVFromExtern,
+	{
+		(!self.is_empty()).then(|| unsafe { self.get_unchecked(0) })
+	}
+
+	/// Get the last element, `None` if the Vector is empty
+	#[inline]
+	pub fn last(&self) -> Option<T>
+	where
+		T: OpenCVFromExtern,
+	{
+		let len = self.len();
+		(len > 0).then(|| unsafe { self.get_unchecked(len - 1) })
+	}
+
+	/// Shorten the Vector to `len` elements, does nothing if the Vector is already shorter
+	#[inline]
+	pub fn truncate(&mut self, len: size_t) {
+		for index in (len..self.len()).rev() {
+			unsafe { self.extern_remove(index) }
+		}
+	}
+
+	/// Resize the Vector to `new_len` elements, either by truncating it or by adding clones of `value` to the end
+	pub fn resize<'a>(&mut self, new_len: size_t, value: <T as OpenCVType<'a>>::Arg)
+	where
+		T: for<'t> OpenCVType<'t>,
+		<T as OpenCVType<'a>>::Arg: Clone,
+	{
+		let len = self.len();
+		if new_len > len {
+			self.reserve(new_len - len);
+			for _ in len..new_len {
+				self.push(value.clone());
+			}
+		} else {
+			self.truncate(new_len);
+		}
+	}
+
+	/// Retain only the elements for which `f` returns `true`, the order of the retained elements is preserved
+	pub fn retain(&mut self, mut f: impl FnMut(&T) -> bool)
+	where
+		T: OpenCVFromExtern,
+	{
+		let mut retained = 0;
+		for index in 0..self.len() {
+			if f(&unsafe { self.get_unchecked(index) }) {
+				if retained != index {
+					unsafe { self.extern_swap(retained, index) }
+				}
+				retained += 1;
+			}
+		}
+		self.truncate(retained);
+	}
+
+	/// Remove the consecutive repeated elements
+	#[inline]
+	pub fn dedup(&mut self)
+	where
+		T: OpenCVFromExtern + PartialEq,
+	{
+		self.dedup_by(|a, b| a == b)
+	}
+
+	/// Remove the consecutive elements for which `same_bucket` returns `true`, only the first one of them is retained
+	pub fn dedup_by(&mut self, mut same_bucket: impl FnMut(&T, &T) -> bool)
+	where
+		T: OpenCVFromExtern,
+	{
+		let mut prev = if let Some(first) = self.first() {
+			first
+		} else {
+			return;
+		};
+		let mut retained = 1;
+		for index in 1..self.len() {
+			let elem = unsafe { self.get_unchecked(index) };
+			if !same_bucket(&elem, &prev) {
+				if retained != index {
+					unsafe { self.extern_swap(retained, index) }
+				}
+				retained += 1;
+				prev = elem;
+			}
+		}
+		self.truncate(retained);
+	}
+
+	/// Remove the specified `range` of elements from the Vector and return them as an iterator
+	///
+	/// Unlike `Vec::drain()` the elements are removed immediately, even if the returned iterator is not consumed.
+	#[inline]
+	pub fn drain(&mut self, range: impl RangeBounds<size_t>) -> Result<VectorIterator<T>>
+	where
+		T: OpenCVIntoExternContainer + OpenCVFromExtern,
+	{
+		let (start, end) = vector_range_check(range, self.len())?;
+		Ok(self.take_range(start, end).into_iter())
+	}
+
+	/// Split the Vector in two at the specified index, returns the Vector with the elements `[at, len)`
+	#[inline]
+	pub fn split_off(&mut self, at: size_t) -> Result<Self>
+	where
+		T: OpenCVIntoExternContainer + OpenCVFromExtern,
+	{
+		let (start, end) = vector_range_check(at.., self.len())?;
+		Ok(self.take_range(start, end))
+	}
+
+	/// Move all the elements of `other` to the end of the Vector, leaving `other` empty
+	pub fn append(&mut self, other: &mut Self)
+	where
+		T: OpenCVIntoExternContainer + OpenCVFromExtern,
+	{
+		let other_len = other.len();
+		self.reserve(other_len);
+		for index in 0..other_len {
+			self.push_owned(unsafe { other.get_unchecked(index) });
+		}
+		other.clear();
+	}
+
+	/// Add copies of all elements of the slice to the end of the Vector
+	pub fn extend_from_slice(&mut self, s: &[T])
+	where
+		Self: VectorExternCopyNonBool<T>,
+		T: OpenCVIntoExternContainer + Clone,
+	{
+		self.reserve(s.len());
+		for elem in s {
+			self.push_owned(elem.clone());
+		}
+	}
+
+	fn take_range(&mut self, start: size_t, end: size_t) -> Self
+	where
+		T: OpenCVIntoExternContainer + OpenCVFromExtern,
+	{
+		let len = self.len();
+		let mut out = Self::with_capacity(end - start);
+		for index in start..end {
+			out.push_owned(unsafe { self.get_unchecked(index) });
+		}
+		// move the tail to the place of the taken elements
+		for index in end..len {
+			unsafe { self.extern_swap(index - (end - start), index) }
+		}
+		self.truncate(len - (end - start));
+		out
+	}
+
+	/// Sort the elements by rearranging them with swaps on the C++ side, used for the types that are not accessible as a
+	/// slice
+	///
+	/// The comparator works on the elements read with `get_unchecked()` once upfront, so this makes one temporary copy of each
+	/// element that is returned by value.
+	pub(crate) fn sort_by_swaps(&mut self, mut compare: impl FnMut(&T, &T) -> Ordering)
+	where
+		T: OpenCVFromExtern,
+	{
+		let elems = self.iter().collect::<Vec<_>>();
+		let mut order = (0..elems.len()).collect::<Vec<_>>();
+		order.sort_by(|&a, &b| compare(&elems[a], &elems[b]));
+		// the element at `order[i]` goes to the position `i`, every permutation cycle is applied by a chain of swaps
+		let mut placed = vec![false; order.len()];
+		for start in 0..order.len() {
+			let mut current = start;
+			while !placed[current] {
+				placed[current] = true;
+				let next = order[current];
+				if next == start {
+					break;
+				}
+				unsafe { self.extern_swap(current, next) }
+				current = next;
+			}
+		}
+	}
+
 	#[inline]
 	pub fn iter(&self) -> VectorRefIterator<T> {
 		VectorRefIterator::new(self)
@@ -297,6 +490,28 @@ where
 	}
 }
 
+impl<T, I: SliceIndex<[T]>> Index<I> for Vector<T>
+where
+	Self: VectorExtern<T> + VectorExternCopyNonBool<T>,
+{
+	type Output = I::Output;
+
+	#[inline]
+	fn index(&self, index: I) -> &Self::Output {
+		&self.as_slice()[index]
+	}
+}
+
+impl<T, I: SliceIndex<[T]>> IndexMut<I> for Vector<T>
+where
+	Self: VectorExtern<T> + VectorExternCopyNonBool<T>,
+{
+	#[inline]
+	fn index_mut(&mut self, index: I) -> &mut Self::Output {
+		&mut self.as_mut_slice()[index]
+	}
+}
+
 impl<T> AsRef<[T]> for Vector<T>
 where
 	Self: VectorExtern<T> + VectorExternCopyNonBool<T>,
@@ -391,6 +606,32 @@ where
 	}
 }
 
+/// Returns the `(start, end)` of the `range` if it's within `0..=len`
+#[inline]
+fn vector_range_check(range: impl RangeBounds<size_t>, len: size_t) -> Result<(size_t, size_t)> {
+	let start = match range.start_bound() {
+		Bound::Included(&start) => Some(start),
+		Bound::Excluded(&start) => start.checked_add(1),
+		Bound::Unbounded => Some(0),
+	};
+	let end = match range.end_bound() {
+		Bound::Included(&end) => end.checked_add(1),
+		Bound::Excluded(&end) => Some(end),
+		Bound::Unbounded => Some(len),
+	};
+	match (start, end) {
+		(Some(start), Some(end)) if start <= end && end <= len => Ok((start, end)),
+		_ => Err(crate::Error::new(
+			crate::core::StsOutOfRange,
+			format!(
+				"Range: {:?}..{:?} out of bounds: 0..{len}",
+				range.start_bound(),
+				range.end_bound()
+			),
+		)),
+	}
+}
+
 #[inline(always)]
 fn vector_index_check(index: size_t, len: size_t) -> Result<()> {
 	if index >= len {
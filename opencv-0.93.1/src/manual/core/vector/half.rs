@@ -0,0 +1,198 @@
+use std::cmp::Ordering;
+use std::ffi::c_void;
+
+use half::f16;
+
+use crate::boxed_ref::{BoxedRef, BoxedRefMut};
+use crate::core::{
+	ToInputArray, ToInputOutputArray, ToOutputArray, Vector, VectorExtern, VectorExternCopyNonBool, VectorToVec, _InputArray,
+	_InputOutputArray, _OutputArray,
+};
+use crate::platform_types::size_t;
+use crate::traits::{Boxed, OpenCVFromExtern};
+use crate::{
+	extern_arg_send, extern_container_send, extern_receive, input_array_ref_forward, output_array_ref_forward, sys, Result,
+};
+
+// std::vector<cv::float16_t> is not used in the OpenCV API so the bindings for it are not generated, see manual-core.cpp
+extern "C" {
+	fn std_vectorLcv_float16_tG_new_const() -> *mut c_void;
+	fn std_vectorLcv_float16_tG_delete(instance: *mut c_void);
+	fn std_vectorLcv_float16_tG_len_const(instance: *const c_void) -> size_t;
+	fn std_vectorLcv_float16_tG_isEmpty_const(instance: *const c_void) -> bool;
+	fn std_vectorLcv_float16_tG_capacity_const(instance: *const c_void) -> size_t;
+	fn std_vectorLcv_float16_tG_shrinkToFit(instance: *mut c_void);
+	fn std_vectorLcv_float16_tG_reserve_size_t(instance: *mut c_void, additional: size_t);
+	fn std_vectorLcv_float16_tG_remove_size_t(instance: *mut c_void, index: size_t);
+	fn std_vectorLcv_float16_tG_swap_size_t_size_t(instance: *mut c_void, index1: size_t, index2: size_t);
+	fn std_vectorLcv_float16_tG_clear(instance: *mut c_void);
+	fn std_vectorLcv_float16_tG_push_const_float16_t(instance: *mut c_void, val: f16);
+	fn std_vectorLcv_float16_tG_insert_size_t_const_float16_t(instance: *mut c_void, index: size_t, val: f16);
+	fn std_vectorLcv_float16_tG_get_const_size_t(instance: *const c_void, index: size_t, ocvrs_return: *mut f16);
+	fn std_vectorLcv_float16_tG_set_size_t_const_float16_t(instance: *mut c_void, index: size_t, val: f16);
+	fn std_vectorLcv_float16_tG_data_const(instance: *const c_void) -> *const f16;
+	fn std_vectorLcv_float16_tG_dataMut(instance: *mut c_void) -> *mut f16;
+	fn cv_fromSlice_const_const_float16_tX_size_t(data: *const f16, len: size_t) -> *mut c_void;
+	fn std_vectorLcv_float16_tG_clone_const(instance: *const c_void) -> *mut c_void;
+	fn std_vectorLcv_float16_tG_inputArray_const(instance: *const c_void, ocvrs_return: *mut sys::Result<*mut c_void>);
+	fn std_vectorLcv_float16_tG_outputArray(instance: *mut c_void, ocvrs_return: *mut sys::Result<*mut c_void>);
+	fn std_vectorLcv_float16_tG_inputOutputArray(instance: *mut c_void, ocvrs_return: *mut sys::Result<*mut c_void>);
+}
+
+impl VectorExtern<f16> for Vector<f16> {
+	#[inline]
+	unsafe fn extern_new() -> extern_receive!(Self) {
+		std_vectorLcv_float16_tG_new_const()
+	}
+
+	#[inline]
+	unsafe fn extern_delete(&mut self) {
+		std_vectorLcv_float16_tG_delete(self.as_raw_mut())
+	}
+
+	#[inline]
+	unsafe fn extern_len(&self) -> size_t {
+		std_vectorLcv_float16_tG_len_const(self.as_raw())
+	}
+
+	#[inline]
+	unsafe fn extern_is_empty(&self) -> bool {
+		std_vectorLcv_float16_tG_isEmpty_const(self.as_raw())
+	}
+
+	#[inline]
+	unsafe fn extern_capacity(&self) -> size_t {
+		std_vectorLcv_float16_tG_capacity_const(self.as_raw())
+	}
+
+	#[inline]
+	unsafe fn extern_shrink_to_fit(&mut self) {
+		std_vectorLcv_float16_tG_shrinkToFit(self.as_raw_mut())
+	}
+
+	#[inline]
+	unsafe fn extern_reserve(&mut self, additional: size_t) {
+		std_vectorLcv_float16_tG_reserve_size_t(self.as_raw_mut(), additional)
+	}
+
+	#[inline]
+	unsafe fn extern_remove(&mut self, index: size_t) {
+		std_vectorLcv_float16_tG_remove_size_t(self.as_raw_mut(), index)
+	}
+
+	#[inline]
+	unsafe fn extern_swap(&mut self, index1: size_t, index2: size_t) {
+		std_vectorLcv_float16_tG_swap_size_t_size_t(self.as_raw_mut(), index1, index2)
+	}
+
+	#[inline]
+	unsafe fn extern_clear(&mut self) {
+		std_vectorLcv_float16_tG_clear(self.as_raw_mut())
+	}
+
+	#[inline]
+	unsafe fn extern_get(&self, index: size_t) -> extern_receive!(f16) {
+		return_send!(via ocvrs_return);
+		std_vectorLcv_float16_tG_get_const_size_t(self.as_raw(), index, ocvrs_return.as_mut_ptr());
+		return_receive!(ocvrs_return => ret);
+		ret
+	}
+
+	#[inline]
+	unsafe fn extern_push(&mut self, val: extern_arg_send!(f16: '_)) {
+		std_vectorLcv_float16_tG_push_const_float16_t(self.as_raw_mut(), val)
+	}
+
+	#[inline]
+	unsafe fn extern_push_owned(&mut self, val: extern_container_send!(f16)) {
+		std_vectorLcv_float16_tG_push_const_float16_t(self.as_raw_mut(), val)
+	}
+
+	#[inline]
+	unsafe fn extern_insert(&mut self, index: size_t, val: extern_arg_send!(f16: '_)) {
+		std_vectorLcv_float16_tG_insert_size_t_const_float16_t(self.as_raw_mut(), index, val)
+	}
+
+	#[inline]
+	unsafe fn extern_set(&mut self, index: size_t, val: extern_arg_send!(f16: '_)) {
+		std_vectorLcv_float16_tG_set_size_t_const_float16_t(self.as_raw_mut(), index, val)
+	}
+}
+
+impl VectorToVec for Vector<f16> {
+	type Element = f16;
+
+	#[inline]
+	fn to_vec(&self) -> Vec<Self::Element> {
+		self.as_slice().to_vec()
+	}
+}
+
+impl Vector<f16> {
+	/// Sort the elements with the comparator function, the sort is stable
+	#[inline]
+	pub fn sort_by(&mut self, compare: impl FnMut(&f16, &f16) -> Ordering) {
+		self.as_mut_slice().sort_by(compare)
+	}
+}
+
+impl Clone for Vector<f16> {
+	#[inline]
+	fn clone(&self) -> Self {
+		unsafe { Self::opencv_from_extern(std_vectorLcv_float16_tG_clone_const(self.as_raw())) }
+	}
+}
+
+impl VectorExternCopyNonBool<f16> for Vector<f16> {
+	#[inline]
+	unsafe fn extern_data(&self) -> *const f16 {
+		std_vectorLcv_float16_tG_data_const(self.as_raw())
+	}
+
+	#[inline]
+	unsafe fn extern_data_mut(&mut self) -> *mut f16 {
+		std_vectorLcv_float16_tG_dataMut(self.as_raw_mut())
+	}
+
+	#[inline]
+	unsafe fn extern_from_slice(data: *const f16, len: size_t) -> extern_receive!(Self) {
+		cv_fromSlice_const_const_float16_tX_size_t(data, len)
+	}
+}
+
+impl ToInputArray for Vector<f16> {
+	#[inline]
+	fn input_array(&self) -> Result<BoxedRef<_InputArray>> {
+		return_send!(via ocvrs_return);
+		unsafe { std_vectorLcv_float16_tG_inputArray_const(self.as_raw(), ocvrs_return.as_mut_ptr()) };
+		return_receive!(unsafe ocvrs_return => ret);
+		let ret = ret.into_result()?;
+		Ok(unsafe { BoxedRef::<_InputArray>::opencv_from_extern(ret) })
+	}
+}
+
+input_array_ref_forward! { Vector<f16> }
+
+impl ToOutputArray for Vector<f16> {
+	#[inline]
+	fn output_array(&mut self) -> Result<BoxedRefMut<_OutputArray>> {
+		return_send!(via ocvrs_return);
+		unsafe { std_vectorLcv_float16_tG_outputArray(self.as_raw_mut(), ocvrs_return.as_mut_ptr()) };
+		return_receive!(unsafe ocvrs_return => ret);
+		let ret = ret.into_result()?;
+		Ok(unsafe { BoxedRefMut::<_OutputArray>::opencv_from_extern(ret) })
+	}
+}
+
+impl ToInputOutputArray for Vector<f16> {
+	#[inline]
+	fn input_output_array(&mut self) -> Result<BoxedRefMut<_InputOutputArray>> {
+		return_send!(via ocvrs_return);
+		unsafe { std_vectorLcv_float16_tG_inputOutputArray(self.as_raw_mut(), ocvrs_return.as_mut_ptr()) };
+		return_receive!(unsafe ocvrs_return => ret);
+		let ret = ret.into_result()?;
+		Ok(unsafe { BoxedRefMut::<_InputOutputArray>::opencv_from_extern(ret) })
+	}
+}
+
+output_array_ref_forward! { Vector<f16> }
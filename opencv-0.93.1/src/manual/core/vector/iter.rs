@@ -1,3 +1,4 @@
+use std::fmt;
 use std::iter::FusedIterator;
 
 use crate::core::{Vector, VectorExtern};
@@ -118,6 +119,18 @@ where
 
 impl<T: OpenCVFromExtern> FusedIterator for VectorIterator<T> where Vector<T>: VectorExtern<T> {}
 
+impl<T: OpenCVFromExtern + fmt::Debug> fmt::Debug for VectorIterator<T>
+where
+	Vector<T>: VectorExtern<T>,
+{
+	/// Lists the remaining elements
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_list()
+			.entries((self.start..self.end).map(|index| unsafe { self.vec.get_unchecked(index) }))
+			.finish()
+	}
+}
+
 pub struct VectorRefIterator<'v, T>
 where
 	Vector<T>: VectorExtern<T>,
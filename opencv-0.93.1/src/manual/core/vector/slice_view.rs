@@ -0,0 +1,101 @@
+use std::ffi::c_void;
+use std::fmt;
+use std::ops::Deref;
+
+use crate::boxed_ref::BoxedRef;
+use crate::core::{DataType, ToInputArray, Vector, VectorExtern, VectorExternCopyNonBool, _InputArray};
+use crate::traits::Boxed;
+use crate::{sys, Result};
+
+extern "C" {
+	fn cv__InputArray__InputArray_const_voidX_int_int(
+		data: *const c_void,
+		n: i32,
+		typ: i32,
+		ocvrs_return: *mut sys::Result<*mut c_void>,
+	);
+}
+
+/// Read-only view of a Rust slice that can be passed to OpenCV functions in place of a `Vector<T>` input
+///
+/// Created by [Vector::with_slice_view], see there for details.
+#[derive(Clone, Copy)]
+pub struct VectorSliceView<'s, T> {
+	slice: &'s [T],
+}
+
+impl<'s, T: DataType> VectorSliceView<'s, T> {
+	/// Returns the viewed slice
+	#[inline]
+	pub fn as_slice(&self) -> &'s [T] {
+		self.slice
+	}
+}
+
+impl<T> Vector<T>
+where
+	Self: VectorExtern<T> + VectorExternCopyNonBool<T>,
+	T: DataType,
+{
+	/// Creates a view of the Rust slice that can be passed as an input array without copying it into a `Vector`
+	///
+	/// The `std::vector` behind `Vector` always owns its buffer, so a `Vec` can't be adopted by a `Vector` without copying,
+	/// and vice versa. When the data is only needed as an input for an OpenCV function, this view avoids both the allocation
+	/// and the copy:
+	/// ```no_run
+	/// # use opencv::core::{Point2f, Vector};
+	/// # use opencv::imgproc;
+	/// # fn main() -> opencv::Result<()> {
+	/// let points = vec![Point2f::new(0., 0.), Point2f::new(10., 0.), Point2f::new(10., 10.)];
+	/// let area = imgproc::contour_area(&Vector::with_slice_view(&points), false)?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	/// OpenCV sees the data as a single-row `Mat` with the element type of `T`, so the view is accepted wherever a single
+	/// `InputArray` is expected, but not as an `InputArrayOfArrays`. Use [Vector::as_slice] for the zero-copy access to the
+	/// contents of an existing `Vector` from Rust.
+	#[inline]
+	pub fn with_slice_view(slice: &[T]) -> VectorSliceView<T> {
+		VectorSliceView { slice }
+	}
+}
+
+impl<T: DataType> ToInputArray for VectorSliceView<'_, T> {
+	#[inline]
+	fn input_array(&self) -> Result<BoxedRef<_InputArray>> {
+		let n = i32::try_from(self.slice.len())?;
+		return_send!(via ocvrs_return);
+		unsafe {
+			cv__InputArray__InputArray_const_voidX_int_int(
+				self.slice.as_ptr().cast::<c_void>(),
+				n,
+				T::opencv_type(),
+				ocvrs_return.as_mut_ptr(),
+			)
+		};
+		return_receive!(unsafe ocvrs_return => ret);
+		ret.into_result().map(|ptr| unsafe { _InputArray::from_raw(ptr) }.into())
+	}
+}
+
+impl<T: DataType> ToInputArray for &VectorSliceView<'_, T> {
+	#[inline]
+	fn input_array(&self) -> Result<BoxedRef<_InputArray>> {
+		(*self).input_array()
+	}
+}
+
+impl<T> Deref for VectorSliceView<'_, T> {
+	type Target = [T];
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		self.slice
+	}
+}
+
+impl<T: fmt::Debug> fmt::Debug for VectorSliceView<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(self.slice, f)
+	}
+}
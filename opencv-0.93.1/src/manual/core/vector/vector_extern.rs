@@ -194,6 +194,14 @@ macro_rules! vector_copy_non_bool {
 			}
 		}
 
+		impl $crate::core::Vector<$type> {
+			/// Sort the elements with the comparator function, the sort is stable
+			#[inline]
+			pub fn sort_by(&mut self, compare: impl FnMut(&$type, &$type) -> std::cmp::Ordering) {
+				self.as_mut_slice().sort_by(compare)
+			}
+		}
+
 		impl $crate::core::VectorExternCopyNonBool<$type> for $crate::core::Vector<$type> {
 			#[inline]
 			unsafe fn extern_data(&self) -> *const $type {
@@ -239,6 +247,17 @@ macro_rules! vector_non_copy_or_bool {
 				(0..self.len()).map(|x| unsafe { self.get_unchecked(x) }).collect()
 			}
 		}
+
+		impl $crate::core::Vector<$type> {
+			/// Sort the elements with the comparator function, the sort is stable
+			///
+			/// Every element is read once to be passed to the comparator, which copies the elements that are returned by value (e.g.
+			/// `String` or nested `Vector`), then the elements are rearranged by swapping them on the C++ side without copying.
+			#[inline]
+			pub fn sort_by(&mut self, compare: impl FnMut(&$type, &$type) -> std::cmp::Ordering) {
+				self.sort_by_swaps(compare)
+			}
+		}
 	};
 }
 
@@ -254,5 +273,22 @@ macro_rules! vector_boxed_ref {
 				(0..self.len()).map(|x| unsafe { self.get_unchecked(x) }).collect()
 			}
 		}
+
+		impl<'b> $crate::core::Vector<$crate::boxed_ref::BoxedRef<'b, $type>> {
+			/// Sort the elements with the comparator function, the sort is stable
+			///
+			/// Every element is read once to be passed to the comparator, which copies the elements that are returned by value (e.g.
+			/// `String` or nested `Vector`), then the elements are rearranged by swapping them on the C++ side without copying.
+			#[inline]
+			pub fn sort_by(
+				&mut self,
+				compare: impl FnMut(
+					&$crate::boxed_ref::BoxedRef<'b, $type>,
+					&$crate::boxed_ref::BoxedRef<'b, $type>,
+				) -> std::cmp::Ordering,
+			) {
+				self.sort_by_swaps(compare)
+			}
+		}
 	};
 }
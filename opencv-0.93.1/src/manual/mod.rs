@@ -4,6 +4,8 @@ pub mod core;
 pub mod dnn;
 pub mod sys;
 pub mod types;
+#[cfg(ocvrs_has_module_videoio)]
+pub mod videoio;
 
 pub mod prelude {
 	#[cfg(all(ocvrs_has_module_core, ocvrs_opencv_branch_32))]
@@ -1,16 +1,16 @@
 // note to self, you can't use union here to store both result and error code because C++ side doesn't
 // support non-POD types as union fields
 
-use std::{ffi::c_void, marker::PhantomData, mem::MaybeUninit};
+use std::{ffi::c_char, ffi::c_void, marker::PhantomData, mem::MaybeUninit};
 
-use crate::templ::receive_string;
 use crate::types::Unit;
 use crate::Error;
 
 #[repr(C)]
 pub struct Result<S, O = S> {
 	pub error_code: i32,
-	pub error_msg: *mut c_void,
+	/// Boxed [Error] created by `ocvrs_create_error()`, `NULL` on success
+	pub error: *mut c_void,
 	pub result: MaybeUninit<S>,
 	_p: PhantomData<O>,
 }
@@ -18,17 +18,28 @@ pub struct Result<S, O = S> {
 impl<S: Into<O>, O> Result<S, O> {
 	#[inline]
 	pub fn into_result(self) -> crate::Result<O> {
-		if self.error_msg.is_null() {
+		if self.error.is_null() {
 			Ok(unsafe { self.result.assume_init() }.into())
 		} else {
-			let error_msg = if self.error_msg.is_null() {
-				"Unable to receive error message".to_string()
-			} else {
-				unsafe { receive_string(self.error_msg.cast::<String>()) }
-			};
-			Err(Error::new(self.error_code, error_msg))
+			Err(*unsafe { Box::from_raw(self.error.cast::<Error>()) })
 		}
 	}
 }
 
+/// Details of the caught C++ exception, mirrors `OcvrsException` in `ocvrs_common.hpp`
+#[repr(C)]
+pub struct Exception {
+	pub code: i32,
+	pub msg: *const c_char,
+	/// See `ExceptionKind`
+	pub kind: i32,
+	/// Name of the C++ wrapper function that caught the exception
+	pub binding: *const c_char,
+	/// The following fields are only set for `cv::Exception`
+	pub func: *const c_char,
+	pub file: *const c_char,
+	pub line: i32,
+	pub err: *const c_char,
+}
+
 pub type ResultVoid = Result<Unit, ()>;
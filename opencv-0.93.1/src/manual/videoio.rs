@@ -0,0 +1,5 @@
+#[cfg(feature = "async")]
+pub use capture_stream::*;
+
+#[cfg(feature = "async")]
+mod capture_stream;
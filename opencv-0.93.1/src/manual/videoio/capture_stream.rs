@@ -0,0 +1,197 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::thread::{self, JoinHandle};
+
+use futures_core::Stream;
+
+use crate::core::Mat;
+use crate::videoio::{VideoCapture, VideoCaptureTrait};
+use crate::{core, sync, Error, Result};
+
+/// What to do with the newly captured frame when the buffer of [VideoCaptureStream] is full
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropPolicy {
+	/// Discard the oldest buffered frame to make room for the new one, the consumer always gets the most recent frames
+	DropOldest,
+	/// Discard the newly captured frame, the consumer gets the frames in the order they were captured without gaps until the
+	/// buffer overflows. The capture error is never discarded, it replaces the last buffered frame instead.
+	DropNewest,
+}
+
+struct State {
+	buffer: VecDeque<Result<Mat>>,
+	finished: bool,
+	dropped_frames: usize,
+	waker: Option<Waker>,
+}
+
+struct Shared {
+	state: Mutex<State>,
+	stop: AtomicBool,
+}
+
+impl Shared {
+	fn state(&self) -> MutexGuard<State> {
+		sync::lock(&self.state)
+	}
+
+	fn push(&self, frame: Result<Mat>, capacity: usize, policy: DropPolicy) {
+		let mut state = self.state();
+		if state.buffer.len() >= capacity {
+			state.dropped_frames += 1;
+			match policy {
+				DropPolicy::DropOldest => {
+					state.buffer.pop_front();
+				}
+				// the error ends the stream, so it replaces the last buffered frame instead of being lost
+				DropPolicy::DropNewest if frame.is_err() => {
+					state.buffer.pop_back();
+				}
+				DropPolicy::DropNewest => return,
+			}
+		}
+		state.buffer.push_back(frame);
+		if let Some(waker) = state.waker.take() {
+			waker.wake();
+		}
+	}
+
+	fn finish(&self) {
+		let mut state = self.state();
+		state.finished = true;
+		if let Some(waker) = state.waker.take() {
+			waker.wake();
+		}
+	}
+}
+
+/// Asynchronous stream of the frames captured from a [VideoCapture]
+///
+/// The frames are read on a dedicated thread into the bounded buffer, when the buffer is full the frames are dropped according
+/// to the [DropPolicy]. The stream ends when [VideoCaptureTrait::read] returns `false` (e.g. at the end of a video file) or
+/// fails, in the latter case the error is yielded as the last item. Dropping the stream (or calling
+/// [VideoCaptureStream::close]) stops the capture thread after the current frame is read and waits for the thread to finish,
+/// the `VideoCapture` is released on that thread.
+/// ```no_run
+/// # use futures::StreamExt;
+/// # use opencv::videoio::{DropPolicy, VideoCapture, VideoCaptureStream, CAP_ANY};
+/// # async fn run() -> opencv::Result<()> {
+/// let capture = VideoCapture::new(0, CAP_ANY)?;
+/// let mut frames = VideoCaptureStream::new(capture, 4, DropPolicy::DropOldest)?;
+/// while let Some(frame) = frames.next().await {
+///     let frame = frame?;
+///     // process the frame
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct VideoCaptureStream {
+	shared: Arc<Shared>,
+	thread: Option<JoinHandle<()>>,
+}
+
+impl VideoCaptureStream {
+	/// Starts the capture thread, `buffer_size` is the maximum number of frames that are kept until consumed
+	pub fn new(mut capture: VideoCapture, buffer_size: usize, policy: DropPolicy) -> Result<Self> {
+		if buffer_size == 0 {
+			return Err(Error::new(core::StsBadArg, "Buffer size must be greater than 0"));
+		}
+		let shared = Arc::new(Shared {
+			state: Mutex::new(State {
+				buffer: VecDeque::with_capacity(buffer_size),
+				finished: false,
+				dropped_frames: 0,
+				waker: None,
+			}),
+			stop: AtomicBool::new(false),
+		});
+		let thread = thread::Builder::new()
+			.name("opencv-video-capture".to_string())
+			.spawn({
+				let shared = Arc::clone(&shared);
+				move || {
+					while !shared.stop.load(Ordering::Acquire) {
+						let mut frame = Mat::default();
+						match capture.read(&mut frame) {
+							Ok(true) => shared.push(Ok(frame), buffer_size, policy),
+							Ok(false) => break,
+							Err(e) => {
+								shared.push(Err(e), buffer_size, policy);
+								break;
+							}
+						}
+					}
+					// release the device before signaling the end of the stream
+					drop(capture);
+					shared.finish();
+				}
+			})
+			.map_err(|e| Error::new(core::StsError, format!("Can't start the capture thread: {e}")))?;
+		Ok(Self {
+			shared,
+			thread: Some(thread),
+		})
+	}
+
+	/// Requests the capture thread to stop, the frames that are already buffered are still yielded before the stream ends
+	#[inline]
+	pub fn stop(&self) {
+		self.shared.stop.store(true, Ordering::Release);
+	}
+
+	/// Stops the capture thread and waits for it to finish, the `VideoCapture` is released when this returns
+	///
+	/// The frames that are already buffered are still yielded before the stream ends. Fails if the capture thread panicked.
+	pub fn close(&mut self) -> Result<()> {
+		self.stop();
+		if let Some(thread) = self.thread.take() {
+			thread
+				.join()
+				.map_err(|_| Error::new(core::StsError, "The capture thread panicked"))?;
+		}
+		Ok(())
+	}
+
+	/// Returns `true` if the capture thread has finished and no more frames will be added to the buffer
+	#[inline]
+	pub fn is_finished(&self) -> bool {
+		self.shared.state().finished
+	}
+
+	/// Number of frames discarded because the buffer was full
+	#[inline]
+	pub fn dropped_frames(&self) -> usize {
+		self.shared.state().dropped_frames
+	}
+}
+
+impl Stream for VideoCaptureStream {
+	type Item = Result<Mat>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let mut state = self.shared.state();
+		if let Some(frame) = state.buffer.pop_front() {
+			Poll::Ready(Some(frame))
+		} else if state.finished {
+			Poll::Ready(None)
+		} else {
+			state.waker = Some(cx.waker().clone());
+			Poll::Pending
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let state = self.shared.state();
+		(state.buffer.len(), state.finished.then_some(state.buffer.len()))
+	}
+}
+
+impl Drop for VideoCaptureStream {
+	fn drop(&mut self) {
+		// the panic of the capture thread has already been reported by the panic hook
+		let _ = self.close();
+	}
+}
@@ -0,0 +1,10 @@
+use std::sync::{Mutex, MutexGuard};
+
+/// Locks the `mutex` ignoring the poisoning
+///
+/// Use only for the data that is never left in an inconsistent state by a panic while the lock is held, e.g. when it's only
+/// modified by the single calls to the methods of the std collections.
+#[inline]
+pub(crate) fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<T> {
+	mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
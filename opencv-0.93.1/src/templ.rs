@@ -1,7 +1,9 @@
 use std::ffi::{c_char, CStr};
 use std::slice;
 
+use crate::manual::sys;
 use crate::platform_types::size_t;
+use crate::Error;
 
 macro_rules! extern_container_arg {
 	(nofail mut $name: ident) => {
@@ -31,12 +33,25 @@ macro_rules! string_arg_output_receive {
 }
 
 macro_rules! callback_arg {
+	($tr_name: ident($($tr_arg_name: ident: $tr_arg_type: ty),*) -> $tr_ret: ty => $tr_userdata_name: ident in $callbacks_name: ident once => $callback_name: ident($($fw_arg_name: ident: $fw_arg_type: ty),*) -> $fw_ret: ty) => {
+		// the callback is called exactly once so it takes the ownership of the closure
+		unsafe extern "C" fn trampoline($($tr_arg_name: $tr_arg_type),*) -> $tr_ret {
+			let callback = $crate::callbacks::take_userdata::<Box<dyn FnMut($($fw_arg_type),*) -> $fw_ret + Send + Sync>>($tr_userdata_name);
+			$crate::callbacks::call(&callback, |callback| callback($($fw_arg_name),*))
+		}
+
+		let $tr_name = if $callback_name.is_some() {
+			Some(trampoline as unsafe extern "C" fn($($tr_arg_name: $tr_arg_type),*) -> $tr_ret)
+		} else {
+			None
+		};
+	};
 	($tr_name: ident($($tr_arg_name: ident: $tr_arg_type: ty),*) -> $tr_ret: ty => $tr_userdata_name: ident in $callbacks_name: ident => $callback_name: ident($($fw_arg_name: ident: $fw_arg_type: ty),*) -> $fw_ret: ty) => {
+		// the closure can be dropped from the registry while it's running (e.g. it replaces itself), so the call holds its own
+		// reference to it
 		unsafe extern "C" fn trampoline($($tr_arg_name: $tr_arg_type),*) -> $tr_ret {
-			let mut callback: Box<Box<dyn FnMut($($fw_arg_type),*) -> $fw_ret + Send + Sync>> = Box::from_raw($tr_userdata_name.cast());
-			let out = callback($($fw_arg_name),*);
-			Box::into_raw(callback);
-			out
+			let callback = $crate::callbacks::clone_userdata::<Box<dyn FnMut($($fw_arg_type),*) -> $fw_ret + Send + Sync>>($tr_userdata_name);
+			$crate::callbacks::call(&callback, |callback| callback($($fw_arg_name),*))
 		}
 
 		let $tr_name = if $callback_name.is_some() {
@@ -44,15 +59,22 @@ macro_rules! callback_arg {
 		} else {
 			None
 		};
-	}
+	};
 }
 
 macro_rules! userdata_arg {
+	// the closure is stored in the callback registry after the call succeeds: `$callbacks_name.register()`
+	($userdata_name: ident in $callbacks_name: ident[$key: expr] => $callback_name: ident) => {
+		let $callbacks_name = $crate::callbacks::Registration::new($key, $callback_name);
+		let $userdata_name = $callbacks_name.userdata();
+	};
+	// the closure is never dropped, known leaks: `highgui::create_button`, `face::FacemarkTrain::set_face_detector`,
+	// `viz::Viz3d::register_keyboard_callback`, `viz::Viz3d::register_mouse_callback`
 	($userdata_name: ident in $callbacks_name: ident => $callback_name: ident) => {
 		let $userdata_name = if let Some(callback) = $callback_name {
-			Box::into_raw(Box::new(callback)).cast::<::std::ffi::c_void>()
+			$crate::callbacks::into_userdata(callback)
 		} else {
-			::std::ptr::null_mut() // fixme, remove previous callback
+			::std::ptr::null_mut()
 		};
 	};
 }
@@ -131,6 +153,12 @@ pub unsafe fn ocvrs_create_byte_string(v: *const u8, len: size_t) -> *mut Vec<u8
 	Box::into_raw(Box::new(v))
 }
 
+/// The return type of this function goes into `sys::Result::error`
+#[inline]
+pub unsafe fn ocvrs_create_error(exception: *const sys::Exception) -> *mut Error {
+	Box::into_raw(Box::new(Error::from_exception(&*exception)))
+}
+
 /// Used for both regular `String` and byte string (`Vec<u8>`)
 #[inline]
 pub unsafe fn receive_string<T>(s: *mut T) -> T {
@@ -0,0 +1,110 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::callbacks::{self, Callback, CallbackKey, CallbackRegistry};
+
+struct DropCounter(Arc<AtomicUsize>);
+
+impl Drop for DropCounter {
+	fn drop(&mut self) {
+		self.0.fetch_add(1, Ordering::SeqCst);
+	}
+}
+
+type MouseCallback = Box<dyn FnMut(i32) + Send + Sync>;
+
+fn mouse_key(window: &str) -> CallbackKey {
+	CallbackKey::MouseCallback {
+		window: window.to_string(),
+	}
+}
+
+fn counted_callback(drops: &Arc<AtomicUsize>) -> MouseCallback {
+	let counter = DropCounter(Arc::clone(drops));
+	Box::new(move |_pos: i32| {
+		let _ = &counter;
+	})
+}
+
+#[test]
+fn callback_registry_drop() {
+	let drops = Arc::new(AtomicUsize::new(0));
+	let callback = || Callback::new(counted_callback(&drops));
+	let mut registry = CallbackRegistry::default();
+
+	// replacement
+	assert!(registry.insert(mouse_key("win1"), callback()).is_none());
+	let previous = registry.insert(mouse_key("win1"), callback());
+	assert!(previous.is_some());
+	assert_eq!(0, drops.load(Ordering::SeqCst));
+	drop(previous);
+	assert_eq!(1, drops.load(Ordering::SeqCst));
+	assert!(registry.contains_key(&mouse_key("win1")));
+
+	// explicit removal
+	drop(registry.remove(&mouse_key("win1")));
+	assert_eq!(2, drops.load(Ordering::SeqCst));
+	assert!(!registry.contains_key(&mouse_key("win1")));
+	assert!(registry.remove(&mouse_key("win1")).is_none());
+
+	// window destruction
+	let trackbar_key = CallbackKey::Trackbar {
+		window: "win1".to_string(),
+		trackbar: "bar".to_string(),
+	};
+	let opengl_key = CallbackKey::OpenGlDrawCallback {
+		window: "win2".to_string(),
+	};
+	registry.insert(mouse_key("win1"), callback());
+	registry.insert(trackbar_key.clone(), callback());
+	registry.insert(opengl_key.clone(), callback());
+	drop(registry.remove_window("win1"));
+	assert_eq!(4, drops.load(Ordering::SeqCst));
+	assert!(!registry.contains_key(&mouse_key("win1")));
+	assert!(!registry.contains_key(&trackbar_key));
+	assert!(registry.contains_key(&opengl_key));
+
+	drop(registry.remove_all());
+	assert_eq!(5, drops.load(Ordering::SeqCst));
+	assert!(!registry.contains_key(&opengl_key));
+
+	drop(registry.insert(mouse_key("win3"), callback()));
+	drop(registry);
+	assert_eq!(6, drops.load(Ordering::SeqCst));
+}
+
+/// Mimics the trampoline generated by `callback_arg!` for a closure that unregisters itself while running
+#[test]
+fn callback_dropped_after_call() {
+	let drops = Arc::new(AtomicUsize::new(0));
+	let mut registry = Some(Callback::new({
+		let counter = DropCounter(Arc::clone(&drops));
+		let drops = Arc::clone(&drops);
+		Box::new(move |pos: i32| {
+			let _ = &counter;
+			assert_eq!(0, drops.load(Ordering::SeqCst));
+			assert_eq!(42, pos);
+		}) as MouseCallback
+	}));
+	let userdata = registry.as_ref().map_or(std::ptr::null_mut(), Callback::userdata);
+
+	let callback = unsafe { callbacks::clone_userdata::<MouseCallback>(userdata) };
+	// the registry entry goes away, e.g. the closure called `destroy_window()`
+	drop(registry.take());
+	assert_eq!(0, drops.load(Ordering::SeqCst));
+	callbacks::call(&callback, |callback| callback(42));
+	assert_eq!(0, drops.load(Ordering::SeqCst));
+	drop(callback);
+	assert_eq!(1, drops.load(Ordering::SeqCst));
+}
+
+#[test]
+fn callback_reentrant_call() {
+	type BoolCallback = Box<dyn FnMut() -> bool + Send + Sync>;
+	let userdata = callbacks::into_userdata(Box::new(|| true) as BoolCallback);
+	let callback = unsafe { callbacks::take_userdata::<BoolCallback>(userdata) };
+	assert!(callbacks::call(&callback, |cb| cb()));
+	// the closure is already running, so the nested call is skipped and returns the default value
+	let nested = callbacks::call(&callback, |_| callbacks::call(&callback, |cb| cb()));
+	assert!(!nested);
+}
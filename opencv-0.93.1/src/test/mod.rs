@@ -1 +1,2 @@
+mod callbacks;
 mod sys;
@@ -255,3 +255,6 @@ opencv_type_copy! {
 	isize, usize,
 	*const c_void, *mut c_void,
 }
+
+#[cfg(feature = "half")]
+opencv_type_copy! { half::f16 }
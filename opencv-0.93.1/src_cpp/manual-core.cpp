@@ -1,3 +1,4 @@
+#include <cstring>
 #include "core.hpp"
 
 template struct Result<void*>;
@@ -79,3 +80,277 @@ extern "C" {
 	void cv_Vec18d_output_array(cv::Vec<double, 18>* instance, Result<void*>* ocvrs_return) { return ocvrs_output_array(instance, ocvrs_return); }
 	void cv_Vec18d_input_output_array(cv::Vec<double, 18>* instance, Result<void*>* ocvrs_return) { return ocvrs_input_output_array(instance, ocvrs_return); }
 }
+
+// Same as the `_InputArray(const _Tp* vec, int n)` constructor, but with the element type supplied at runtime
+class OcvrsInputArraySlice : public cv::_InputArray {
+public:
+	OcvrsInputArraySlice(const void* data, int n, int type) {
+		init(FIXED_TYPE + FIXED_SIZE + MATX + CV_MAT_TYPE(type) + cv::ACCESS_READ, data, cv::Size(n, 1));
+	}
+};
+
+extern "C" {
+	void cv__InputArray__InputArray_const_voidX_int_int(const void* data, int n, int type, Result<void*>* ocvrs_return) {
+		try {
+			Ok<void*>(new cv::_InputArray(OcvrsInputArraySlice(data, n, type)), ocvrs_return);
+		} OCVRS_CATCH(ocvrs_return)
+	}
+}
+
+extern "C" {
+	int cv_Mat_refcount_const(const cv::Mat* instance) {
+		return instance->u ? instance->u->refcount : 0;
+	}
+}
+
+#if CV_VERSION_MAJOR >= 4
+typedef cv::AccessFlag ocvrs_access_flag_t;
+#else
+typedef int ocvrs_access_flag_t;
+#endif
+
+typedef void* (*OcvrsMatAllocateFn)(void* ctx, size_t size);
+typedef void (*OcvrsMatDeallocateFn)(void* ctx, void* data, size_t size);
+typedef void (*OcvrsMatMapFn)(void* ctx, void* data, size_t size, int access_flags);
+typedef void (*OcvrsMatUnmapFn)(void* ctx, void* data, size_t size);
+
+// Adapts the Rust `MatAllocator` trait into cv::MatAllocator, the data layout logic mirrors cv::StdMatAllocator
+class OcvrsMatAllocator : public cv::MatAllocator {
+	void* ctx;
+	OcvrsMatAllocateFn allocate_fn;
+	OcvrsMatDeallocateFn deallocate_fn;
+	OcvrsMatMapFn map_fn;
+	OcvrsMatUnmapFn unmap_fn;
+
+public:
+	OcvrsMatAllocator(void* ctx, OcvrsMatAllocateFn allocate_fn, OcvrsMatDeallocateFn deallocate_fn, OcvrsMatMapFn map_fn, OcvrsMatUnmapFn unmap_fn)
+		: ctx(ctx), allocate_fn(allocate_fn), deallocate_fn(deallocate_fn), map_fn(map_fn), unmap_fn(unmap_fn) {}
+
+	cv::UMatData* allocate(int dims, const int* sizes, int type, void* data0, size_t* step, ocvrs_access_flag_t, cv::UMatUsageFlags) const CV_OVERRIDE {
+		size_t total = CV_ELEM_SIZE(type);
+		for (int i = dims - 1; i >= 0; i--) {
+			if (step) {
+				if (data0 && step[i] != CV_AUTOSTEP) {
+					total = step[i];
+				} else {
+					step[i] = total;
+				}
+			}
+			total *= sizes[i];
+		}
+		uchar* data = data0 ? static_cast<uchar*>(data0) : static_cast<uchar*>(allocate_fn(ctx, total));
+		if (!data) {
+			CV_Error_(cv::Error::StsNoMem, ("Failed to allocate %llu bytes", static_cast<unsigned long long>(total)));
+		}
+		cv::UMatData* u = new cv::UMatData(this);
+		u->data = u->origdata = data;
+		u->size = total;
+		if (data0) {
+			u->flags |= cv::UMatData::USER_ALLOCATED;
+		}
+		return u;
+	}
+
+	bool allocate(cv::UMatData* u, ocvrs_access_flag_t, cv::UMatUsageFlags) const CV_OVERRIDE {
+		return u != NULL;
+	}
+
+	void deallocate(cv::UMatData* u) const CV_OVERRIDE {
+		if (!u) {
+			return;
+		}
+		CV_Assert(u->urefcount == 0);
+		CV_Assert(u->refcount == 0);
+		if (!(u->flags & cv::UMatData::USER_ALLOCATED)) {
+			deallocate_fn(ctx, u->origdata, u->size);
+			u->origdata = 0;
+		}
+		delete u;
+	}
+
+	void map(cv::UMatData* u, ocvrs_access_flag_t access_flags) const CV_OVERRIDE {
+		map_fn(ctx, u->data, u->size, static_cast<int>(access_flags));
+	}
+
+	void unmap(cv::UMatData* u) const CV_OVERRIDE {
+		unmap_fn(ctx, u->data, u->size);
+		// same as the base cv::MatAllocator::unmap()
+		if (u->urefcount == 0 && u->refcount == 0) {
+			deallocate(u);
+		}
+	}
+};
+
+extern "C" {
+	cv::MatAllocator* cv_MatAllocator_new(void* ctx, OcvrsMatAllocateFn allocate_fn, OcvrsMatDeallocateFn deallocate_fn, OcvrsMatMapFn map_fn, OcvrsMatUnmapFn unmap_fn) {
+		return new OcvrsMatAllocator(ctx, allocate_fn, deallocate_fn, map_fn, unmap_fn);
+	}
+
+	void cv_Mat_setDefaultAllocator_MatAllocatorX(cv::MatAllocator* allocator, ResultVoid* ocvrs_return) {
+		try {
+			cv::Mat::setDefaultAllocator(allocator ? allocator : cv::Mat::getStdAllocator());
+			Ok(ocvrs_return);
+		} OCVRS_CATCH(ocvrs_return)
+	}
+}
+
+typedef void (*OcvrsReleaseFn)(void* ctx);
+
+struct OcvrsExternalData {
+	void* ctx;
+	OcvrsReleaseFn release;
+};
+
+// Owner of the data that was not allocated by OpenCV (e.g. a memory-mapped file), `release` is called when the last Mat
+// referencing the data is released
+class OcvrsExternalDataAllocator : public cv::MatAllocator {
+public:
+	cv::UMatData* allocate(int, const int*, int, void*, size_t*, ocvrs_access_flag_t, cv::UMatUsageFlags) const CV_OVERRIDE {
+		CV_Error(cv::Error::StsNotImplemented, "External data can't be reallocated");
+		return NULL;
+	}
+
+	bool allocate(cv::UMatData* u, ocvrs_access_flag_t, cv::UMatUsageFlags) const CV_OVERRIDE {
+		return u != NULL;
+	}
+
+	void deallocate(cv::UMatData* u) const CV_OVERRIDE {
+		if (!u) {
+			return;
+		}
+		CV_Assert(u->urefcount == 0);
+		CV_Assert(u->refcount == 0);
+		OcvrsExternalData* external = static_cast<OcvrsExternalData*>(u->userdata);
+		external->release(external->ctx);
+		delete external;
+		delete u;
+	}
+};
+
+extern "C" {
+	void cv_Mat_fromExternalData_int_const_intX_int_voidX_voidX_ReleaseFn(int ndims, const int* sizes, int type, void* data, void* ctx, OcvrsReleaseFn release, Result<void*>* ocvrs_return) {
+		static OcvrsExternalDataAllocator allocator;
+		try {
+			cv::Mat* ret = new cv::Mat(ndims, sizes, type, data);
+			OcvrsExternalData* external = new OcvrsExternalData;
+			external->ctx = ctx;
+			external->release = release;
+			cv::UMatData* u = new cv::UMatData(&allocator);
+			u->data = u->origdata = static_cast<uchar*>(data);
+			u->size = ret->total() * ret->elemSize();
+			u->refcount = 1;
+			u->userdata = external;
+			ret->u = u;
+			Ok<void*>(ret, ocvrs_return);
+		} OCVRS_CATCH(ocvrs_return)
+	}
+}
+
+// std::vector<cv::float16_t> is not used in the OpenCV API so it's not generated, but it's needed for Vector<half::f16>
+#if !(CV_VERSION_MAJOR == 3 && CV_VERSION_MINOR == 2)
+#if (CV_VERSION_MAJOR == 4 && CV_VERSION_MINOR >= 10) /* 4.10+ */ \
+	|| (CV_VERSION_MAJOR > 4) /* 5.0+ */
+typedef cv::hfloat ocvrs_float16_t;
+#else
+typedef cv::float16_t ocvrs_float16_t;
+#endif
+static_assert(sizeof(ocvrs_float16_t) == sizeof(unsigned short), "Half float must be 16 bits");
+
+inline ocvrs_float16_t ocvrs_float16_from_bits(unsigned short bits) {
+	ocvrs_float16_t out;
+	std::memcpy(&out, &bits, sizeof(out));
+	return out;
+}
+
+inline unsigned short ocvrs_float16_to_bits(ocvrs_float16_t val) {
+	unsigned short out;
+	std::memcpy(&out, &val, sizeof(out));
+	return out;
+}
+
+extern "C" {
+	std::vector<ocvrs_float16_t>* std_vectorLcv_float16_tG_new_const() {
+		return new std::vector<ocvrs_float16_t>();
+	}
+
+	void std_vectorLcv_float16_tG_delete(std::vector<ocvrs_float16_t>* instance) {
+		delete instance;
+	}
+
+	size_t std_vectorLcv_float16_tG_len_const(const std::vector<ocvrs_float16_t>* instance) {
+		return instance->size();
+	}
+
+	bool std_vectorLcv_float16_tG_isEmpty_const(const std::vector<ocvrs_float16_t>* instance) {
+		return instance->empty();
+	}
+
+	size_t std_vectorLcv_float16_tG_capacity_const(const std::vector<ocvrs_float16_t>* instance) {
+		return instance->capacity();
+	}
+
+	void std_vectorLcv_float16_tG_shrinkToFit(std::vector<ocvrs_float16_t>* instance) {
+		instance->shrink_to_fit();
+	}
+
+	void std_vectorLcv_float16_tG_reserve_size_t(std::vector<ocvrs_float16_t>* instance, size_t additional) {
+		instance->reserve(instance->size() + additional);
+	}
+
+	void std_vectorLcv_float16_tG_remove_size_t(std::vector<ocvrs_float16_t>* instance, size_t index) {
+		instance->erase(instance->begin() + index);
+	}
+
+	void std_vectorLcv_float16_tG_swap_size_t_size_t(std::vector<ocvrs_float16_t>* instance, size_t index1, size_t index2) {
+		std::swap((*instance)[index1], (*instance)[index2]);
+	}
+
+	void std_vectorLcv_float16_tG_clear(std::vector<ocvrs_float16_t>* instance) {
+		instance->clear();
+	}
+
+	void std_vectorLcv_float16_tG_push_const_float16_t(std::vector<ocvrs_float16_t>* instance, const unsigned short val) {
+		instance->push_back(ocvrs_float16_from_bits(val));
+	}
+
+	void std_vectorLcv_float16_tG_insert_size_t_const_float16_t(std::vector<ocvrs_float16_t>* instance, size_t index, const unsigned short val) {
+		instance->insert(instance->begin() + index, ocvrs_float16_from_bits(val));
+	}
+
+	void std_vectorLcv_float16_tG_get_const_size_t(const std::vector<ocvrs_float16_t>* instance, size_t index, unsigned short* ocvrs_return) {
+		*ocvrs_return = ocvrs_float16_to_bits((*instance)[index]);
+	}
+
+	void std_vectorLcv_float16_tG_set_size_t_const_float16_t(std::vector<ocvrs_float16_t>* instance, size_t index, const unsigned short val) {
+		(*instance)[index] = ocvrs_float16_from_bits(val);
+	}
+
+	const ocvrs_float16_t* std_vectorLcv_float16_tG_data_const(const std::vector<ocvrs_float16_t>* instance) {
+		return instance->data();
+	}
+
+	ocvrs_float16_t* std_vectorLcv_float16_tG_dataMut(std::vector<ocvrs_float16_t>* instance) {
+		return instance->data();
+	}
+
+	std::vector<ocvrs_float16_t>* cv_fromSlice_const_const_float16_tX_size_t(const ocvrs_float16_t* data, size_t len) {
+		return new std::vector<ocvrs_float16_t>(data, data + len);
+	}
+
+	std::vector<ocvrs_float16_t>* std_vectorLcv_float16_tG_clone_const(const std::vector<ocvrs_float16_t>* instance) {
+		return new std::vector<ocvrs_float16_t>(*instance);
+	}
+
+	void std_vectorLcv_float16_tG_inputArray_const(const std::vector<ocvrs_float16_t>* instance, Result<void*>* ocvrs_return) {
+		ocvrs_input_array(instance, ocvrs_return);
+	}
+
+	void std_vectorLcv_float16_tG_outputArray(std::vector<ocvrs_float16_t>* instance, Result<void*>* ocvrs_return) {
+		ocvrs_output_array(instance, ocvrs_return);
+	}
+
+	void std_vectorLcv_float16_tG_inputOutputArray(std::vector<ocvrs_float16_t>* instance, Result<void*>* ocvrs_return) {
+		ocvrs_input_output_array(instance, ocvrs_return);
+	}
+}
+#endif
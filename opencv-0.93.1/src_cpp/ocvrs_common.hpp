@@ -16,19 +16,44 @@
 #endif
 
 #include <memory>
+#include <new>
 #include <opencv2/core.hpp>
 
 #define OCVRS_ONLY_DEPENDENT_TYPES
 
-#define OCVRS_HANDLE(code, msg, return_name) Err(code, msg, return_name)
+// mirrors `ExceptionKind` in error.rs
+enum OcvrsExceptionKind {
+	OCVRS_EXCEPTION_OPENCV = 1,
+	OCVRS_EXCEPTION_BAD_ALLOC = 2,
+	OCVRS_EXCEPTION_STD = 3,
+	OCVRS_EXCEPTION_UNKNOWN = 4,
+};
+
+// mirrors `sys::Exception` in sys.rs
+struct OcvrsException {
+	int code;
+	const char* msg;
+	int kind;
+	const char* binding;
+	// the following fields are only set for cv::Exception
+	const char* func;
+	const char* file;
+	int line;
+	const char* err;
+};
+
+#define OCVRS_HANDLE(code, msg, kind, return_name) Err(OcvrsException { code, msg, kind, __func__, NULL, NULL, 0, NULL }, return_name)
 
+// __func__ is the name of the wrapper function where the macro is expanded, it's passed to Rust to identify the binding
 #define OCVRS_CATCH(return_name) \
 catch (cv::Exception& e) { \
-	OCVRS_HANDLE(e.code, e.what(), return_name); \
-} catch (std::exception &e) { \
-	OCVRS_HANDLE(cv::Error::StsError, e.what(), return_name); \
+	Err(OcvrsException { e.code, e.what(), OCVRS_EXCEPTION_OPENCV, __func__, e.func.c_str(), e.file.c_str(), e.line, e.err.c_str() }, return_name); \
+} catch (std::bad_alloc& e) { \
+	OCVRS_HANDLE(cv::Error::StsNoMem, e.what(), OCVRS_EXCEPTION_BAD_ALLOC, return_name); \
+} catch (std::exception& e) { \
+	OCVRS_HANDLE(cv::Error::StsError, e.what(), OCVRS_EXCEPTION_STD, return_name); \
 } catch (...) { \
-	OCVRS_HANDLE(cv::Error::StsError, "Unspecified error, neither from OpenCV nor from std", return_name); \
+	OCVRS_HANDLE(cv::Error::StsError, "Unspecified error, neither from OpenCV nor from std", OCVRS_EXCEPTION_UNKNOWN, return_name); \
 }
 
 // double-expansion macro trick to expand the OCVRS_FFI_EXPORT_SUFFIX macro
@@ -38,37 +63,39 @@ catch (cv::Exception& e) { \
 // defined in build/generator/collector.rs Collector::inject_ffi_exports, see `inject_ffi_exports()` function for explanation
 extern "C" void* SUFFIXED_NAME(ocvrs_create_string, OCVRS_FFI_EXPORT_SUFFIX)(const char*);
 extern "C" void* SUFFIXED_NAME(ocvrs_create_byte_string, OCVRS_FFI_EXPORT_SUFFIX)(const char*, size_t);
+extern "C" void* SUFFIXED_NAME(ocvrs_create_error, OCVRS_FFI_EXPORT_SUFFIX)(const OcvrsException*);
 
 // "aliases" for the above functions provided by Rust, to be used in the generated code
 inline void* ocvrs_create_string(const char* s) { return SUFFIXED_NAME(ocvrs_create_string, OCVRS_FFI_EXPORT_SUFFIX)(s); }
 inline void* ocvrs_create_byte_string(const char* s, size_t len) { return SUFFIXED_NAME(ocvrs_create_byte_string, OCVRS_FFI_EXPORT_SUFFIX)(s, len); }
+inline void* ocvrs_create_error(const OcvrsException& e) { return SUFFIXED_NAME(ocvrs_create_error, OCVRS_FFI_EXPORT_SUFFIX)(&e); }
 
 template<typename T> struct Result {
 	int error_code;
-	void* error_msg;
+	void* error;
 	T result;
 };
 
 struct ResultVoid {
 	int error_code;
-	void* error_msg;
+	void* error;
 };
 
 template<typename T, typename R> inline void Ok(T result, Result<R>* ocvrs_return) {
 	ocvrs_return->error_code = 0;
-	ocvrs_return->error_msg = NULL;
+	ocvrs_return->error = NULL;
 	ocvrs_return->result = *const_cast<R*>(&result);
 }
 
 inline void Ok(ResultVoid* ocvrs_return) {
 	ocvrs_return->error_code = 0;
-	ocvrs_return->error_msg = NULL;
+	ocvrs_return->error = NULL;
 }
 
-template<typename T> inline void Err(int code, const char* msg, T* ocvrs_return) {
-	ocvrs_return->error_code = code;
-	ocvrs_return->error_msg = ocvrs_create_string(msg);
-	// it's ok to leave result uninitialized because the Rust implementation only assumes it as init if error_msg is NULL
+template<typename T> inline void Err(const OcvrsException& e, T* ocvrs_return) {
+	ocvrs_return->error_code = e.code;
+	ocvrs_return->error = ocvrs_create_error(e);
+	// it's ok to leave result uninitialized because the Rust implementation only assumes it as init if error is NULL
 }
 
 #endif
@@ -0,0 +1,91 @@
+use opencv::core::{self, CV_VERSION};
+use opencv::{BuildInfo, Result};
+
+const BUILD_INFORMATION: &str = "
+General configuration for OpenCV 4.10.0 =====================================
+  Version control:               4.10.0
+
+  CPU/HW features:
+    Baseline:                    SSE SSE2 SSE3
+      requested:                 SSE3
+    Dispatched code generation:  SSE4_1 AVX2
+      requested:                 SSE4_1 SSE4_2 AVX FP16 AVX2
+      SSE4_1 (16 files):         + SSSE3 SSE4_1
+
+  OpenCV modules:
+    To be built:                 calib3d core dnn features2d flann highgui imgcodecs imgproc
+                                 video videoio
+    Disabled:                    world
+
+  Video I/O:
+    DC1394:                      NO
+    FFMPEG:                      YES
+      avcodec:                   YES (60.31.102)
+    GStreamer:                   YES (1.24.4)
+
+  Parallel framework:            pthreads
+
+  Other third-party libraries:
+    Intel IPP:                   2021.11.0 [2021.11.0]
+           at:                   /build/3rdparty/ippicv/ippicv_lnx/icv
+    Lapack:                      NO
+
+  OpenCL:                        YES (no extra features)
+    Include path:                /build/3rdparty/include/opencl/1.2
+
+  Install to:                    /usr/local
+-----------------------------------------------------------------
+";
+
+#[test]
+fn build_info_parse() {
+	let info = BuildInfo::parse(BUILD_INFORMATION);
+	assert_eq!("4.10.0", info.version);
+	assert_eq!(
+		[
+			"calib3d",
+			"core",
+			"dnn",
+			"features2d",
+			"flann",
+			"highgui",
+			"imgcodecs",
+			"imgproc",
+			"video",
+			"videoio"
+		],
+		info.modules.as_slice()
+	);
+	assert!(info.has_module("videoio"));
+	assert!(!info.has_module("world"));
+	assert_eq!(["SSE", "SSE2", "SSE3"], info.cpu_baseline.as_slice());
+	assert_eq!(["SSE4_1", "AVX2"], info.cpu_dispatch.as_slice());
+	assert_eq!(Some("pthreads"), info.parallel_framework.as_deref());
+
+	assert_eq!(3, info.videoio_backends.len());
+	assert!(info.has_videoio_backend("ffmpeg"));
+	assert!(info.has_videoio_backend("GStreamer"));
+	assert!(!info.has_videoio_backend("DC1394"));
+	assert!(!info.has_videoio_backend("MSMF"));
+	let ffmpeg = info.videoio_backend("FFMPEG").unwrap();
+	assert_eq!("YES (60.31.102)", ffmpeg.child("avcodec").unwrap().value);
+
+	assert_eq!(2, info.third_party.len());
+	assert!(info.third_party[0].is_enabled());
+	assert_eq!("Intel IPP", info.third_party[0].name);
+	assert!(!info.third_party[1].is_enabled());
+
+	assert!(info.opencl().unwrap().is_enabled());
+	assert!(info.cuda().is_none());
+	assert_eq!("/usr/local", info.item("Install to").unwrap().value);
+}
+
+#[test]
+fn build_info_runtime() -> Result<()> {
+	let info = opencv::build_info()?;
+	assert_eq!(CV_VERSION, info.version);
+	assert!(info.has_module("core"));
+	assert!(info.crate_modules.contains(&"core"));
+	assert_eq!(BuildInfo::parse(&core::get_build_information()?), info);
+	Ok(())
+}
@@ -0,0 +1,66 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use opencv::callbacks::{is_registered, unregister, CallbackKey};
+
+struct DropCounter(Arc<AtomicUsize>);
+
+impl Drop for DropCounter {
+	fn drop(&mut self) {
+		self.0.fetch_add(1, Ordering::SeqCst);
+	}
+}
+
+fn mouse_key(window: &str) -> CallbackKey {
+	CallbackKey::MouseCallback {
+		window: window.to_string(),
+	}
+}
+
+#[test]
+fn callback_unregister() {
+	let key = mouse_key("callback_unregister_window");
+	assert!(!is_registered(&key));
+	assert!(!unsafe { unregister(&key) });
+}
+
+/// Goes through the generated setter, so it checks that the closures are actually stored in the registry and dropped
+#[test]
+fn callback_mouse_setter_drop() -> opencv::Result<()> {
+	#![cfg(ocvrs_has_module_highgui)]
+	use opencv::highgui;
+
+	const WINDOW: &str = "callback_mouse_setter_drop";
+	if highgui::named_window_def(WINDOW).is_err() {
+		// no GUI backend available
+		return Ok(());
+	}
+	let drops = Arc::new(AtomicUsize::new(0));
+	let callback = || {
+		let counter = DropCounter(Arc::clone(&drops));
+		Some(Box::new(move |_event: i32, _x: i32, _y: i32, _flags: i32| {
+			let _ = &counter;
+		}) as Box<dyn FnMut(i32, i32, i32, i32) + Send + Sync>)
+	};
+	let key = mouse_key(WINDOW);
+
+	highgui::set_mouse_callback(WINDOW, callback())?;
+	assert!(is_registered(&key));
+	assert_eq!(0, drops.load(Ordering::SeqCst));
+
+	// replacement
+	highgui::set_mouse_callback(WINDOW, callback())?;
+	assert_eq!(1, drops.load(Ordering::SeqCst));
+
+	// reset
+	highgui::set_mouse_callback(WINDOW, None)?;
+	assert_eq!(2, drops.load(Ordering::SeqCst));
+	assert!(!is_registered(&key));
+
+	// window destruction
+	highgui::set_mouse_callback(WINDOW, callback())?;
+	highgui::destroy_window(WINDOW)?;
+	assert_eq!(3, drops.load(Ordering::SeqCst));
+	assert!(!is_registered(&key));
+	Ok(())
+}
@@ -1,6 +1,7 @@
 use opencv::core::{Moments, Point2f, RotatedRect, Scalar, Size2f, Vec3b, Vector, CV_32S, CV_64F, CV_8U, CV_MAKETYPE};
 use opencv::prelude::*;
-use opencv::{core, Result};
+use opencv::error::ExceptionKind;
+use opencv::{core, Error, Result};
 
 #[test]
 fn make_type() {
@@ -99,3 +100,27 @@ fn min_max_loc() -> Result<()> {
 	assert_eq!(30., max_val);
 	Ok(())
 }
+
+#[test]
+fn error_details() -> Result<()> {
+	let a = Mat::new_rows_cols_with_default(2, 2, u8::opencv_type(), 1.into())?;
+	let b = Mat::new_rows_cols_with_default(3, 3, u8::opencv_type(), 1.into())?;
+	let mut dst = Mat::default();
+	let err = core::add_def(&a, &b, &mut dst).unwrap_err();
+	assert_eq!(core::StsUnmatchedSizes, err.code);
+	assert_eq!(ExceptionKind::OpenCV, err.exception_kind());
+	assert!(err.cpp_binding().unwrap().starts_with("cv_add_"));
+	assert!(err.func().map_or(false, |func| !func.is_empty()));
+	assert!(err.file().map_or(false, |file| file.ends_with(".cpp")));
+	assert!(err.line().map_or(false, |line| line > 0));
+	assert!(err.err().map_or(false, |e| err.message.contains(e)));
+	assert!(err.to_string().contains(err.cpp_binding().unwrap()));
+
+	let err = Error::new(core::StsBadArg, "Rust error");
+	assert_eq!(ExceptionKind::Rust, err.exception_kind());
+	assert_eq!(None, err.cpp_binding());
+	assert_eq!(None, err.func());
+	assert_eq!(None, err.line());
+	assert_eq!("Rust error (code: StsBadArg, -5)", err.to_string());
+	Ok(())
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "derive")]
+
+use opencv::core::{Mat, Vec3f, CV_16UC3, CV_32F, CV_32FC1, CV_MAKETYPE};
+use opencv::prelude::*;
+use opencv::Result;
+
+#[derive(Clone, Copy, Debug, PartialEq, DataType)]
+#[repr(C)]
+struct Bgr16 {
+	b: u16,
+	g: u16,
+	r: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, DataType)]
+#[repr(C)]
+struct Gray(f32);
+
+#[derive(Clone, Copy, Debug, PartialEq, DataType)]
+#[repr(C)]
+struct PointNormal {
+	point: Vec3f,
+	normal: Vec3f,
+}
+
+#[test]
+fn data_type_derive() -> Result<()> {
+	assert_eq!(CV_16UC3, Bgr16::opencv_type());
+	assert_eq!(CV_32FC1, Gray::opencv_type());
+	assert_eq!(CV_MAKETYPE(CV_32F, 6), PointNormal::opencv_type());
+
+	let mat = Mat::new_rows_cols_with_default(2, 3, Bgr16::opencv_type(), (1., 2., 3.).into())?;
+	assert_eq!(Bgr16 { b: 1, g: 2, r: 3 }, *mat.at_2d::<Bgr16>(1, 2)?);
+
+	let mat = Mat::from_slice(&[Gray(1.), Gray(2.)])?;
+	assert_eq!(2., mat.at::<Gray>(1)?.0);
+	assert_eq!(&[1., 2.], mat.data_typed::<f32>()?);
+	Ok(())
+}
@@ -133,6 +133,7 @@ fn input_output_array_types() -> Result<()> {
 
 	let slice: &[u8] = &[];
 	check_input(slice, _InputArray_MATX, |m| m.is_matx())?;
+	check_input(Vector::with_slice_view(slice), _InputArray_MATX, |m| m.is_matx())?;
 
 	#[cfg(ocvrs_has_module_cudaimgproc)]
 	{
@@ -4,7 +4,8 @@ use std::{mem, thread};
 use matches::assert_matches;
 
 use opencv::core::{
-	MatConstIterator, MatIter, Point, Point2d, Point2f, Rect, Scalar, Size, Vec2b, Vec2s, Vec3d, Vec3f, Vec3s, Vec4w, Vector,
+	MatConstIterator, MatIter, MatPool, MatPoolStats, Point, Point2d, Point2f, Rect, Scalar, SharedMat, Size, ToInputArray,
+	TypedMat, Vec2b, Vec2s, Vec3b, Vec3d, Vec3f, Vec3s, Vec4b, Vec4w, Vector,
 };
 use opencv::prelude::*;
 use opencv::{core, imgproc, Error, Result};
@@ -796,6 +797,64 @@ fn mat_iterator() -> Result<()> {
 	Ok(())
 }
 
+#[test]
+fn mat_rows_iterator() -> Result<()> {
+	{
+		let mat = Mat::from_slice_2d(&[[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12], [13, 14, 15, 16]])?;
+		let rows = mat.rows_iter::<i32>()?;
+		assert_eq!(4, rows.len());
+		assert_eq!(vec![10, 26, 42, 58], rows.map(|row| row.iter().sum()).collect::<Vec<i32>>());
+		let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
+		assert_eq!(vec![&[6, 7][..], &[10, 11][..]], roi.rows_iter::<i32>()?.collect::<Vec<_>>());
+		assert_eq!(vec![6, 7, 10, 11], roi.iter_fast::<i32>()?.copied().collect::<Vec<_>>());
+		assert_eq!(
+			roi.iter::<i32>()?.map(|(_, x)| x).collect::<Vec<_>>(),
+			roi.iter_fast::<i32>()?.copied().collect::<Vec<_>>()
+		);
+		assert_matches!(
+			mat.rows_iter::<f32>(),
+			Err(Error {
+				code: core::StsUnmatchedFormats,
+				..
+			})
+		);
+	}
+
+	{
+		let mut mat = Mat::from_slice_2d(&[[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12], [13, 14, 15, 16]])?;
+		let mut roi = Mat::roi_mut(&mut mat, Rect::new(1, 1, 2, 2))?;
+		for (row_n, row) in roi.rows_iter_mut::<i32>()?.enumerate() {
+			row.fill(-(row_n as i32));
+		}
+		assert_eq!(
+			[1, 2, 3, 4, 5, 0, 0, 8, 9, -1, -1, 12, 13, 14, 15, 16],
+			mat.data_typed::<i32>()?
+		);
+	}
+
+	{
+		let mut mat = Mat::new_nd_with_default(&[2, 3, 4], u16::opencv_type(), 0.into())?;
+		assert_eq!(6, mat.rows_iter::<u16>()?.len());
+		for (i, x) in mat.data_typed_mut::<u16>()?.iter_mut().enumerate() {
+			*x = i as u16;
+		}
+		let part = mat.slice((.., 1.., 1..3))?;
+		assert!(!part.is_continuous());
+		assert_eq!(
+			vec![&[5, 6][..], &[9, 10][..], &[17, 18][..], &[21, 22][..]],
+			part.rows_iter::<u16>()?.collect::<Vec<_>>()
+		);
+		assert_eq!(8, part.iter_fast::<u16>()?.count());
+	}
+
+	{
+		let mat = Mat::default();
+		assert_eq!(0, mat.rows_iter::<u8>()?.len());
+		assert_eq!(0, mat.iter_fast::<u8>()?.count());
+	}
+	Ok(())
+}
+
 #[test]
 fn mat_locate_roi() -> Result<()> {
 	let mat = Mat::from_slice(&[1, 2, 3, 4])?;
@@ -863,6 +922,151 @@ fn mat_roi_2() -> Result<()> {
 	Ok(())
 }
 
+#[test]
+fn mat_roi_n() -> Result<()> {
+	let mut mat = Mat::new_rows_cols_with_default(4, 4, i32::opencv_type(), 0.into())?;
+	{
+		let mut rois = Mat::roi_n_mut(
+			&mut mat,
+			&[Rect::new(0, 0, 2, 2), Rect::new(2, 0, 2, 2), Rect::new(0, 2, 4, 2)],
+		)?;
+		assert_eq!(3, rois.len());
+		for (i, roi) in rois.iter_mut().enumerate() {
+			roi.set_scalar((i as i32 + 1).into())?;
+		}
+	}
+	assert_eq!(
+		vec![vec![1, 1, 2, 2], vec![1, 1, 2, 2], vec![3, 3, 3, 3], vec![3, 3, 3, 3]],
+		mat.to_vec_2d::<i32>()?
+	);
+
+	assert_matches!(
+		Mat::roi_n_mut(&mut mat, &[Rect::new(0, 0, 2, 2), Rect::new(2, 2, 2, 2), Rect::new(1, 1, 2, 2)]),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_split_rows_and_tiles() -> Result<()> {
+	let mut mat = Mat::new_rows_cols_with_default(5, 7, u8::opencv_type(), 0.into())?;
+	{
+		let bands = Mat::split_rows_mut(&mut mat, 2)?;
+		assert_eq!(vec![Size::new(7, 2), Size::new(7, 3)], bands.iter().map(|b| b.size()).collect::<Result<Vec<_>>>()?);
+		thread::scope(|s| {
+			for (i, mut band) in bands.into_iter().enumerate() {
+				s.spawn(move || band.set_scalar((i as i32 + 1).into()).unwrap());
+			}
+		});
+	}
+	assert_eq!(1, *mat.at_2d::<u8>(1, 6)?);
+	assert_eq!(2, *mat.at_2d::<u8>(2, 0)?);
+	assert_eq!(5, Mat::split_rows_mut(&mut mat, 10)?.len());
+
+	{
+		let tiles = Mat::tiles_mut(&mut mat, Size::new(3, 3))?;
+		assert_eq!(
+			vec![Size::new(3, 3), Size::new(3, 3), Size::new(1, 3), Size::new(3, 2), Size::new(3, 2), Size::new(1, 2)],
+			tiles.iter().map(|t| t.size()).collect::<Result<Vec<_>>>()?
+		);
+		thread::scope(|s| {
+			for mut tile in tiles {
+				s.spawn(move || tile.set_scalar(9.into()).unwrap());
+			}
+		});
+	}
+	assert!(mat.data_typed::<u8>()?.iter().all(|&x| x == 9));
+
+	assert_matches!(
+		Mat::tiles_mut(&mut mat, Size::new(0, 3)),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_slice() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]])?;
+	let part = mat.slice((1.., 1..3))?;
+	assert_eq!(Size::new(2, 2), part.size()?);
+	assert_eq!(vec![vec![6, 7], vec![10, 11]], part.to_vec_2d::<i32>()?);
+
+	let part = mat.slice((..=0,))?;
+	assert_eq!(vec![vec![1, 2, 3, 4]], part.to_vec_2d::<i32>()?);
+
+	let part = mat.slice([0..3, 3..4])?;
+	assert_eq!(vec![vec![4], vec![8], vec![12]], part.to_vec_2d::<i32>()?);
+
+	let mat = Mat::new_nd_with_default(&[3, 4, 5], i32::opencv_type(), 7.into())?;
+	let part = mat.slice((.., 1..3, 4..))?;
+	assert_eq!(&[3, 2, 1], &*part.mat_size());
+	assert_eq!(7, *part.at_3d::<i32>(2, 1, 0)?);
+
+	assert_matches!(
+		mat.slice((0..4,)),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	assert_matches!(
+		mat.slice((1..1,)),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	assert_matches!(
+		mat.slice((.., .., .., ..)),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_slice_mut() -> Result<()> {
+	let mut mat = Mat::from_slice_2d(&[[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]])?;
+	mat.slice_mut((.., 2..))?.set_scalar(0.into())?;
+	assert_eq!(
+		vec![vec![1, 2, 0, 0], vec![5, 6, 0, 0], vec![9, 10, 0, 0]],
+		mat.to_vec_2d::<i32>()?
+	);
+
+	{
+		let (mut top, mut bottom) = mat.split_at_mut(0, 1)?;
+		assert_eq!(Size::new(4, 1), top.size()?);
+		assert_eq!(Size::new(4, 2), bottom.size()?);
+		mem::swap(top.at_2d_mut::<i32>(0, 0)?, bottom.at_2d_mut::<i32>(1, 0)?);
+	}
+	assert_eq!(9, *mat.at_2d::<i32>(0, 0)?);
+	assert_eq!(1, *mat.at_2d::<i32>(2, 0)?);
+
+	assert_matches!(
+		mat.split_at_mut(1, 4),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	assert_matches!(
+		mat.split_at_mut(2, 1),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	Ok(())
+}
+
 #[test]
 fn mat_convert() -> Result<()> {
 	let mat = Mat::from_slice(&[1, 2, 3, 4])?;
@@ -1219,6 +1423,184 @@ fn mat_reshape() -> Result<()> {
 	Ok(())
 }
 
+#[test]
+fn mat_typed() -> Result<()> {
+	let mut mat = TypedMat::<Vec3b, 2>::new([3, 4])?;
+	assert_eq!([3, 4], mat.shape());
+	assert_eq!(Vec3b::opencv_type(), mat.typ());
+	*mat.at_mut([2, 1])? = Vec3b::from([10, 20, 30]);
+	assert_eq!(Vec3b::from([10, 20, 30]), *mat.at([2, 1])?);
+	assert_matches!(
+		mat.at([3, 0]),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+
+	let mat = TypedMat::<f32, 3>::from_slice([2, 2, 2], &[1., 2., 3., 4., 5., 6., 7., 8.])?;
+	assert_eq!([2, 2, 2], mat.shape());
+	assert_eq!(7., *mat.at([1, 1, 0])?);
+	assert_matches!(
+		TypedMat::<f32, 2>::from_slice([2, 2], &[1., 2., 3.]),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+
+	let untyped = Mat::new_rows_cols_with_default(2, 3, u8::opencv_type(), 5.into())?;
+	let typed = TypedMat::<u8, 2>::try_from(untyped.clone())?;
+	assert_eq!([2, 3], typed.shape());
+	assert_eq!(&[5; 6], typed.data_typed()?);
+	assert_matches!(
+		TypedMat::<u16, 2>::try_from(untyped.clone()),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	assert_matches!(
+		TypedMat::<u8, 3>::try_from(untyped),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+
+	// the output type is checked by OpenCV
+	let mut bgr = TypedMat::<Vec3b, 2>::new([2, 3])?;
+	*bgr.at_mut([1, 2])? = Vec3b::from([255, 255, 255]);
+	let mut gray = TypedMat::<u8, 2>::new([0, 0])?;
+	imgproc::cvt_color_def(&bgr, &mut gray, imgproc::COLOR_BGR2GRAY)?;
+	assert_eq!([2, 3], gray.shape());
+	assert_eq!(255, *gray.at([1, 2])?);
+	assert_eq!(0, *gray.at([0, 0])?);
+	let mut gray_f32 = TypedMat::<f32, 2>::new([0, 0])?;
+	assert!(imgproc::cvt_color_def(&bgr, &mut gray_f32, imgproc::COLOR_BGR2GRAY).is_err());
+	Ok(())
+}
+
+#[test]
+fn mat_shared() -> Result<()> {
+	let mut shared = SharedMat::new(Mat::new_rows_cols_with_default(2, 3, u8::opencv_type(), 1.into())?);
+	assert_eq!(1, shared.ref_count());
+	assert!(shared.get_mut().is_some());
+
+	let mut shared2 = shared.clone();
+	assert_eq!(2, shared.ref_count());
+	assert_eq!(2, shared2.ref_count());
+	assert_eq!(shared.data(), shared2.data());
+	assert!(shared2.get_mut().is_none());
+
+	// copy-on-write
+	*shared2.make_mut()?.at_2d_mut::<u8>(1, 2)? = 10;
+	assert_ne!(shared.data(), shared2.data());
+	assert_eq!(1, shared.ref_count());
+	assert_eq!(1, shared2.ref_count());
+	assert_eq!(1, *shared.at_2d::<u8>(1, 2)?);
+	assert_eq!(10, *shared2.at_2d::<u8>(1, 2)?);
+
+	// unique data is modified in place
+	let data = shared.data();
+	*shared.make_mut()?.at_2d_mut::<u8>(0, 0)? = 5;
+	assert_eq!(data, shared.data());
+
+	// shared between threads
+	let consumers = (0..3)
+		.map(|_| {
+			let shared = shared.clone();
+			thread::spawn(move || *shared.at_2d::<u8>(0, 0).unwrap())
+		})
+		.collect::<Vec<_>>();
+	for consumer in consumers {
+		assert_eq!(5, consumer.join().unwrap());
+	}
+	assert_eq!(1, shared.ref_count());
+
+	// unique data is returned as is, shared data is copied
+	let data = shared.data();
+	let mat = shared.into_mat()?;
+	assert_eq!(data, mat.data());
+	let shared = SharedMat::new(mat);
+	let shared2 = shared.clone();
+	assert_eq!(2, shared.ref_count());
+	let mat = shared2.into_mat()?;
+	assert_ne!(shared.data(), mat.data());
+	assert_eq!(5, *mat.at_2d::<u8>(0, 0)?);
+	assert!(shared.is_unique());
+
+	assert_eq!(0, SharedMat::new(Mat::default()).ref_count());
+	Ok(())
+}
+
+#[test]
+fn mat_pool() -> Result<()> {
+	let pool = MatPool::new(2);
+	let size = Size::new(4, 3);
+	let mut mat = pool.get(size, u8::opencv_type())?;
+	assert_eq!(size, mat.size()?);
+	assert_eq!(u8::opencv_type(), mat.typ());
+	assert_eq!(&[0; 12], mat.data_typed::<u8>()?);
+	mat.set_scalar(7.into())?;
+	let data = mat.data();
+	drop(mat);
+	assert_eq!(1, pool.len());
+
+	// the same allocation is reused
+	let mat = pool.get(size, u8::opencv_type())?;
+	assert_eq!(data, mat.data());
+	assert_eq!(&[7; 12], mat.data_typed::<u8>()?);
+	let mat2 = pool.get(size, u8::opencv_type())?;
+	assert_ne!(data, mat2.data());
+	assert_eq!(
+		MatPoolStats {
+			hits: 1,
+			misses: 2,
+			returned: 1,
+			discarded: 0,
+		},
+		pool.stats()
+	);
+
+	// output arrays reuse the pooled allocation, the Mats are returned from other threads
+	let src = Mat::new_rows_cols_with_default(3, 4, Vec3b::opencv_type(), Scalar::all(10.))?;
+	let mut gray = pool.get(size, u8::opencv_type())?;
+	imgproc::cvt_color_def(&src, &mut gray, imgproc::COLOR_BGR2GRAY)?;
+	assert_eq!(10, *gray.at_2d::<u8>(2, 3)?);
+	thread::spawn(move || drop((mat, mat2, gray))).join().unwrap();
+	assert_eq!(2, pool.len());
+	assert_eq!(1, pool.stats().discarded);
+
+	// detached, shared and reallocated Mats are not returned
+	pool.clear();
+	assert!(pool.is_empty());
+	let detached = pool.get(size, u8::opencv_type())?.detach();
+	assert_eq!(size, detached.size()?);
+	let shared = pool.get(size, u8::opencv_type())?;
+	let shallow_copy = shared.input_array()?.get_mat_def()?;
+	drop(shared);
+	drop(shallow_copy);
+	assert!(pool.is_empty());
+	let mut reallocated = pool.get(size, u8::opencv_type())?;
+	imgproc::cvt_color_def(&src, &mut reallocated, imgproc::COLOR_BGR2BGRA)?;
+	drop(reallocated);
+	assert_eq!(1, pool.len());
+	assert_eq!(Vec4b::opencv_type(), pool.get(size, Vec4b::opencv_type())?.typ());
+	assert_eq!(2, pool.stats().hits);
+
+	// 1-dimensional Mats are created as a single column
+	let column = pool.get_nd(&[5], u8::opencv_type())?;
+	let data = column.data();
+	drop(column);
+	assert_eq!(data, pool.get_nd(&[5], u8::opencv_type())?.data());
+	assert_eq!(3, pool.stats().hits);
+
+	pool.preallocate(Size::new(2, 2), f32::opencv_type(), 5)?;
+	assert_eq!(4, pool.len());
+	Ok(())
+}
+
 #[test]
 fn mat_send_sync() -> Result<()> {
 	{
@@ -0,0 +1,41 @@
+use opencv::core::{Scalar, ToInputArray, TrackingMatAllocator, Vec3b};
+use opencv::prelude::*;
+use opencv::Result;
+
+static ALLOCATOR: TrackingMatAllocator = TrackingMatAllocator::new();
+
+/// Single test in this file because the default allocator is global
+#[test]
+fn mat_default_allocator() -> Result<()> {
+	let before = Mat::new_rows_cols_with_default(10, 10, u8::opencv_type(), Scalar::all(1.))?;
+
+	Mat::set_default_allocator(Some(&ALLOCATOR))?;
+	assert_eq!(0, ALLOCATOR.bytes_in_use());
+	let mat = Mat::new_rows_cols_with_default(100, 200, Vec3b::opencv_type(), Scalar::all(2.))?;
+	assert_eq!(100 * 200 * 3, ALLOCATOR.bytes_in_use());
+	assert_eq!(1, ALLOCATOR.allocations());
+	let mut copy = Mat::default();
+	mat.convert_to_def(&mut copy, u16::opencv_type())?;
+	assert_eq!(2, *copy.at_2d::<u16>(99, 199)?);
+	assert_eq!(100 * 200 * 5, ALLOCATOR.bytes_in_use());
+	assert_eq!(2, ALLOCATOR.allocations());
+	// shallow copies don't allocate
+	let roi = mat.row(5)?.input_array()?.get_mat_def()?;
+	assert_eq!(2, ALLOCATOR.allocations());
+	drop(mat);
+	assert_eq!(100 * 200 * 5, ALLOCATOR.bytes_in_use());
+	drop(roi);
+	assert_eq!(100 * 200 * 2, ALLOCATOR.bytes_in_use());
+
+	Mat::set_default_allocator(None)?;
+	let after = Mat::new_rows_cols_with_default(100, 100, u8::opencv_type(), Scalar::all(3.))?;
+	assert_eq!(100 * 200 * 2, ALLOCATOR.bytes_in_use());
+	// the data allocated by the custom allocator is freed by it after the default allocator is reset
+	drop(copy);
+	assert_eq!(0, ALLOCATOR.bytes_in_use());
+	assert_eq!(0, ALLOCATOR.allocations());
+	assert_eq!(100 * 200 * 5, ALLOCATOR.peak_bytes());
+	assert_eq!(1, *before.at_2d::<u8>(9, 9)?);
+	assert_eq!(3, *after.at_2d::<u8>(99, 99)?);
+	Ok(())
+}
@@ -0,0 +1,73 @@
+#![cfg(all(feature = "half", not(ocvrs_opencv_branch_32)))]
+
+use half::f16;
+use matches::assert_matches;
+
+use opencv::core::{Mat_, Vec3f, VecN, Vector};
+use opencv::prelude::*;
+use opencv::{core, Error, Result};
+
+#[test]
+fn mat_f16() -> Result<()> {
+	let data = [f16::from_f32(1.5), f16::from_f32(-2.), f16::from_f32(0.25), f16::MAX];
+	let mut mat = Mat::from_slice(&data)?.try_clone()?;
+	assert_eq!(core::CV_16FC1, mat.typ());
+	assert_eq!(f16::from_f32(-2.), *mat.at::<f16>(1)?);
+	assert_eq!(data, mat.data_typed::<f16>()?);
+	*mat.at_mut::<f16>(0)? = f16::from_f32(3.);
+	assert_eq!(3., mat.at::<f16>(0)?.to_f32());
+
+	let typed = mat.try_into_typed::<f16>()?;
+	assert_eq!(f16::MAX, *typed.at(3)?);
+
+	let mat = Mat::new_rows_cols_with_default(2, 2, VecN::<f16, 3>::opencv_type(), (1., 2., 3.).into())?;
+	assert_eq!(core::CV_16FC3, mat.typ());
+	assert_eq!(f16::from_f32(2.), mat.at_2d::<VecN<f16, 3>>(1, 1)?[1]);
+	let typed = Mat_::<VecN<f16, 3>>::try_from(mat)?;
+	assert_eq!(f16::from_f32(3.), typed.at_2d(0, 1)?[2]);
+	Ok(())
+}
+
+#[test]
+fn mat_f16_f32_conversion() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1.5f32, -2.], [0.25, 100000.]])?;
+	let half = mat.f32_to_f16()?;
+	assert_eq!(core::CV_16FC1, half.typ());
+	assert_eq!(f16::from_f32(0.25), *half.at_2d::<f16>(1, 0)?);
+	assert_eq!(f16::INFINITY, *half.at_2d::<f16>(1, 1)?);
+
+	let back = half.f16_to_f32()?;
+	assert_eq!(core::CV_32FC1, back.typ());
+	assert_eq!(-2., *back.at_2d::<f32>(0, 1)?);
+
+	let mat = Mat::new_rows_cols_with_default(2, 3, Vec3f::opencv_type(), (1., 2., 3.).into())?;
+	assert_eq!(core::CV_16FC3, mat.f32_to_f16()?.typ());
+
+	assert_matches!(
+		mat.f16_to_f32(),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn vector_f16() -> Result<()> {
+	let mut vec = Vector::<f16>::new();
+	vec.push(f16::from_f32(1.));
+	vec.push(f16::from_f32(2.5));
+	vec.insert(0, f16::from_f32(-1.))?;
+	assert_eq!(3, vec.len());
+	assert_eq!(f16::from_f32(2.5), vec.get(2)?);
+	vec.set(1, f16::ONE)?;
+	assert_eq!(&[f16::from_f32(-1.), f16::ONE, f16::from_f32(2.5)], vec.as_slice());
+
+	let vec = Vector::from_slice(&[f16::ZERO, f16::ONE]);
+	assert_eq!(vec![f16::ZERO, f16::ONE], vec.clone().to_vec());
+
+	let mat = Mat::from_exact_iter(vec.iter())?;
+	assert_eq!(core::CV_16FC1, mat.typ());
+	Ok(())
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "image")]
+
+use image::{DynamicImage, GrayImage, ImageBuffer, Luma, Rgb, RgbImage, Rgba};
+use matches::assert_matches;
+
+use opencv::core::{Rect, Vec3b, Vec3f};
+use opencv::prelude::*;
+use opencv::{core, Error, Result};
+
+#[test]
+fn mat_from_image_buffer() -> Result<()> {
+	let mut img = RgbImage::new(4, 3);
+	img.put_pixel(2, 1, Rgb([10, 20, 30]));
+	{
+		let mat = Mat::from_image_buffer(&img)?;
+		assert_eq!(Vec3b::opencv_type(), mat.typ());
+		assert_eq!(3, mat.rows());
+		assert_eq!(4, mat.cols());
+		assert_eq!(Rgb([10, 20, 30]), *mat.at_2d::<Rgb<u8>>(1, 2)?);
+	}
+	{
+		let mut mat = Mat::from_image_buffer_mut(&mut img)?;
+		*mat.at_2d_mut::<Rgb<u8>>(0, 0)? = Rgb([1, 2, 3]);
+	}
+	assert_eq!(&Rgb([1, 2, 3]), img.get_pixel(0, 0));
+
+	let dyn_img = DynamicImage::ImageLuma16(ImageBuffer::from_pixel(2, 2, Luma([500])));
+	let mat = Mat::from_dynamic_image(&dyn_img)?;
+	assert_eq!(u16::opencv_type(), mat.typ());
+	assert_eq!(500, *mat.at_2d::<u16>(1, 1)?);
+	Ok(())
+}
+
+#[test]
+fn mat_image_copy_conversion() -> Result<()> {
+	let mut img = RgbImage::new(4, 3);
+	img.put_pixel(2, 1, Rgb([10, 20, 30]));
+	let mat = Mat::try_from(&img)?;
+	assert_eq!(Vec3b::opencv_type(), mat.typ());
+	assert_eq!(Vec3b::from([30, 20, 10]), *mat.at_2d::<Vec3b>(1, 2)?);
+
+	let img_back = RgbImage::try_from(&mat)?;
+	assert_eq!(img, img_back);
+
+	let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?.try_clone()?;
+	let img_roi = RgbImage::try_from(&roi)?;
+	assert_eq!(2, img_roi.width());
+	assert_eq!(&Rgb([10, 20, 30]), img_roi.get_pixel(1, 0));
+
+	let dyn_img = DynamicImage::try_from(&mat)?;
+	assert_eq!(Some(&img), dyn_img.as_rgb8());
+	let mat_back = Mat::try_from(&dyn_img)?;
+	assert_eq!(mat.data_bytes()?, mat_back.data_bytes()?);
+
+	let mat = Mat::new_rows_cols_with_default(2, 2, Vec3f::opencv_type(), (0.1, 0.2, 0.3).into())?;
+	let dyn_img = DynamicImage::try_from(&mat)?;
+	assert_eq!(&Rgb([0.3, 0.2, 0.1]), dyn_img.as_rgb32f().expect("Must be Rgb32F").get_pixel(0, 0));
+	Ok(())
+}
+
+#[test]
+fn mat_image_conversion_mismatch() -> Result<()> {
+	let mat = Mat::new_rows_cols_with_default(2, 2, Vec3b::opencv_type(), 0.into())?;
+	assert_matches!(
+		GrayImage::try_from(&mat),
+		Err(Error {
+			code: core::BadNumChannels,
+			..
+		})
+	);
+	assert_matches!(
+		ImageBuffer::<Rgba<u16>, Vec<u16>>::try_from(&mat),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+
+	let mat = Mat::new_rows_cols_with_default(2, 2, f64::opencv_type(), 0.into())?;
+	assert_matches!(
+		DynamicImage::try_from(&mat),
+		Err(Error {
+			code: core::StsUnsupportedFormat,
+			..
+		})
+	);
+	Ok(())
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "ndarray")]
+
+use matches::assert_matches;
+use ndarray::{s, Array2, Array3};
+
+use opencv::core::{Rect, Vec3b};
+use opencv::prelude::*;
+use opencv::{core, Error, Result};
+
+#[test]
+fn mat_as_array_view() -> Result<()> {
+	let mat = Mat::new_rows_cols_with_data(2, 3, &[1u16, 2, 3, 4, 5, 6])?;
+	let view = mat.as_array_view::<u16>()?;
+	assert_eq!(&[2, 3], view.shape());
+	assert_eq!(2, view[[0, 1]]);
+	assert_eq!(6, view[[1, 2]]);
+
+	assert_matches!(
+		mat.as_array_view::<f32>(),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+
+	let mat = Mat::default();
+	assert_eq!(0, mat.as_array_view::<u8>()?.len());
+	Ok(())
+}
+
+#[test]
+fn mat_as_array_view_channels() -> Result<()> {
+	let mut mat = Mat::new_rows_cols_with_default(3, 4, Vec3b::opencv_type(), (1., 2., 3.).into())?;
+	{
+		let view = mat.as_array_view::<u8>()?;
+		assert_eq!(&[3, 4, 3], view.shape());
+		assert_eq!(1, view[[2, 3, 0]]);
+		assert_eq!(3, view[[2, 3, 2]]);
+	}
+	{
+		let view = mat.as_array_view::<Vec3b>()?;
+		assert_eq!(&[3, 4], view.shape());
+		assert_eq!(Vec3b::from([1, 2, 3]), view[[1, 1]]);
+	}
+	{
+		let mut view = mat.as_array_view_mut::<u8>()?;
+		view[[1, 2, 1]] = 20;
+	}
+	assert_eq!(Vec3b::from([1, 20, 3]), *mat.at_2d::<Vec3b>(1, 2)?);
+
+	let typed = mat.try_into_typed::<Vec3b>()?;
+	assert_eq!(&[3, 4], typed.as_array_view()?.shape());
+	Ok(())
+}
+
+#[test]
+fn mat_as_array_view_roi() -> Result<()> {
+	let mut mat = Mat::from_slice_2d(&[[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]])?;
+	{
+		let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
+		assert!(!roi.is_continuous());
+		let view = roi.as_array_view::<i32>()?;
+		assert_eq!(&[2, 2], view.shape());
+		assert_eq!(&[4, 1], view.strides());
+		assert_eq!(vec![6, 7, 10, 11], view.iter().copied().collect::<Vec<_>>());
+	}
+	{
+		let mut roi = Mat::roi_mut(&mut mat, Rect::new(2, 0, 2, 3))?;
+		roi.as_array_view_mut::<i32>()?.fill(0);
+	}
+	assert_eq!(vec![vec![1, 2, 0, 0], vec![5, 6, 0, 0], vec![9, 10, 0, 0]], mat.to_vec_2d::<i32>()?);
+	Ok(())
+}
+
+#[test]
+fn mat_from_ndarray() -> Result<()> {
+	let array = Array2::from_shape_fn((3, 4), |(row, col)| (row * 4 + col) as f32);
+	let mat = Mat::from_ndarray(&array)?;
+	assert_eq!(f32::opencv_type(), mat.typ());
+	assert_eq!(3, mat.rows());
+	assert_eq!(4, mat.cols());
+	assert_eq!(5., *mat.at_2d::<f32>(1, 1)?);
+
+	let slice = array.slice(s![1.., 1..3]);
+	let mat = Mat::from_ndarray(&slice)?;
+	assert_eq!(2, mat.rows());
+	assert_eq!(2, mat.cols());
+	assert!(!mat.is_continuous());
+	assert_eq!(vec![vec![5., 6.], vec![9., 10.]], mat.to_vec_2d::<f32>()?);
+
+	assert_matches!(
+		Mat::from_ndarray(&array.t()),
+		Err(Error {
+			code: core::BadStep,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_from_ndarray_channels() -> Result<()> {
+	let mut array = Array3::<u8>::zeros((2, 3, 3));
+	{
+		let mut mat = Mat::from_ndarray_channels_mut(&mut array)?;
+		assert_eq!(Vec3b::opencv_type(), mat.typ());
+		assert_eq!(2, mat.rows());
+		assert_eq!(3, mat.cols());
+		*mat.at_2d_mut::<Vec3b>(1, 2)? = Vec3b::from([7, 8, 9]);
+	}
+	assert_eq!(8, array[[1, 2, 1]]);
+
+	let mat = Mat::from_ndarray_channels(&array)?;
+	assert_eq!(array.view().into_dyn(), mat.as_array_view::<u8>()?);
+
+	assert_matches!(
+		Mat::from_ndarray_channels(&array.slice(s![0, 0, ..])),
+		Err(Error {
+			code: core::StsBadSize,
+			..
+		})
+	);
+	Ok(())
+}
@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use opencv::core::{Scalar, Vec3b, Vec4w, CV_MAKETYPE};
+use opencv::prelude::*;
+use opencv::{core, Result};
+
+fn temp_path(name: &str) -> PathBuf {
+	std::env::temp_dir().join(format!("opencv_rust_{name}_{}", std::process::id()))
+}
+
+#[test]
+fn mat_write_npy() -> Result<()> {
+	let mat = Mat::new_rows_cols_with_default(2, 3, f32::opencv_type(), Scalar::all(1.5))?;
+	let mut npy = vec![];
+	mat.write_npy_to(&mut npy)?;
+	assert_eq!(b"\x93NUMPY\x01\x00", &npy[..8]);
+	let header_len = usize::from(u16::from_le_bytes([npy[8], npy[9]]));
+	assert_eq!(0, (10 + header_len) % 64);
+	let header = std::str::from_utf8(&npy[10..10 + header_len]).unwrap();
+	assert!(header.starts_with("{'descr': '<f4', 'fortran_order': False, 'shape': (2, 3), }"));
+	assert!(header.ends_with('\n'));
+	assert_eq!(mat.data_bytes()?, &npy[10 + header_len..]);
+
+	let mat = Mat::new_rows_cols_with_default(4, 5, Vec3b::opencv_type(), Scalar::all(7.))?;
+	npy.clear();
+	mat.write_npy_to(&mut npy)?;
+	let header = std::str::from_utf8(&npy[10..74]).unwrap();
+	assert!(header.contains("'shape': (4, 5, 3)"));
+	assert_eq!(4 * 5 * 3, npy.len() - 74);
+
+	let roi = mat.col(1)?;
+	assert!(roi.write_npy_to(&mut vec![]).is_err());
+	assert!(Mat::default().write_npy_to(&mut vec![]).is_err());
+	Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn mat_map_npy() -> Result<()> {
+	let path = temp_path("map.npy");
+	let mut mat = Mat::new_nd_with_default(&[3, 4, 5], i16::opencv_type(), Scalar::all(0.))?;
+	for (i, val) in mat.data_typed_mut::<i16>()?.iter_mut().enumerate() {
+		*val = i as i16 - 30;
+	}
+	mat.write_npy(&path)?;
+	let mapped = unsafe { Mat::map_npy(&path) }?;
+	let _ = std::fs::remove_file(&path);
+	// 3-dimensional array with a small last axis is loaded as a 2-dimensional multichannel one
+	assert_eq!(2, mapped.dims());
+	assert_eq!(CV_MAKETYPE(core::CV_16S, 5), mapped.typ());
+	assert_eq!(mat.data_bytes()?, mapped.data_bytes()?);
+
+	let mat = Mat::new_rows_cols_with_default(480, 640, Vec4w::opencv_type(), Scalar::new(1., 2., 3., 4.))?;
+	mat.write_npy(&path)?;
+	let mapped = unsafe { Mat::map_npy(&path) }?;
+	let _ = std::fs::remove_file(&path);
+	assert_eq!(mat.size()?, mapped.size()?);
+	assert_eq!(Vec4w::from([1, 2, 3, 4]), *mapped.at_2d::<Vec4w>(479, 639)?);
+
+	// the mapped data is read-only, a deep copy is needed for the modification
+	let mut copy = mapped.try_clone()?;
+	drop(mapped);
+	copy.set_scalar(Scalar::all(9.))?;
+	assert_eq!(Vec4w::all(9), *copy.at_2d::<Vec4w>(0, 0)?);
+	Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn mat_map_raw() -> Result<()> {
+	let path = temp_path("map.raw");
+	let data = (0..100u8).collect::<Vec<_>>();
+	std::fs::write(&path, &data).unwrap();
+	let mapped = unsafe { Mat::map_raw(&path, &[4, 6], Vec3b::opencv_type(), 16) }?;
+	assert_eq!(&data[16..16 + 4 * 6 * 3], mapped.data_bytes()?);
+	assert_eq!(Vec3b::from([19, 20, 21]), *mapped.at_2d::<Vec3b>(0, 1)?);
+	assert!(unsafe { Mat::map_raw(&path, &[10, 10], u8::opencv_type(), 1) }.is_err());
+	assert!(unsafe { Mat::map_raw(&path, &[2, 2], f32::opencv_type(), 2) }.is_err());
+	let _ = std::fs::remove_file(&path);
+	assert!(unsafe { Mat::map_raw(&path, &[1], u8::opencv_type(), 0) }.is_err());
+	Ok(())
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "rayon")]
+
+use matches::assert_matches;
+use rayon::prelude::*;
+
+use opencv::core::{Rect, Vec3b};
+use opencv::prelude::*;
+use opencv::{core, Error, Result};
+
+#[test]
+fn mat_par_rows() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6], [7, 8, 9]])?;
+	let sums = mat.par_rows::<i32>()?.map(|row| row.iter().sum()).collect::<Vec<i32>>();
+	assert_eq!(vec![6, 15, 24], sums);
+
+	let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
+	let rows = roi.par_rows::<i32>()?.collect::<Vec<_>>();
+	assert_eq!(vec![&[5, 6][..], &[8, 9][..]], rows);
+
+	assert_matches!(
+		mat.par_rows::<u8>(),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+
+	assert_eq!(0, Mat::default().par_rows::<u8>()?.count());
+	Ok(())
+}
+
+#[test]
+fn mat_par_rows_mut() -> Result<()> {
+	let mut mat = Mat::new_rows_cols_with_default(100, 50, i32::opencv_type(), 0.into())?;
+	mat.par_rows_mut::<i32>()?.enumerate().for_each(|(row_n, row)| {
+		row.iter_mut().for_each(|px| *px = row_n as i32);
+	});
+	assert_eq!(99, *mat.at_2d::<i32>(99, 49)?);
+
+	{
+		let mut roi = Mat::roi_mut(&mut mat, Rect::new(10, 10, 5, 5))?;
+		roi.par_rows_mut::<i32>()?.for_each(|row| row.fill(-1));
+	}
+	assert_eq!(-1, *mat.at_2d::<i32>(14, 14)?);
+	assert_eq!(15, *mat.at_2d::<i32>(15, 14)?);
+	assert_eq!(14, *mat.at_2d::<i32>(14, 15)?);
+
+	let mut mat = Mat::new_nd_with_default(&[3, 4, 5], u8::opencv_type(), 0.into())?;
+	assert_eq!(12, mat.par_rows_mut::<u8>()?.len());
+	mat.par_rows_mut::<u8>()?.for_each(|row| {
+		assert_eq!(5, row.len());
+		row[4] = 1;
+	});
+	assert_eq!(1, *mat.at_3d::<u8>(2, 3, 4)?);
+	assert_eq!(0, *mat.at_3d::<u8>(2, 3, 3)?);
+	Ok(())
+}
+
+#[test]
+fn mat_par_pixels_mut() -> Result<()> {
+	let mut mat = Mat::new_rows_cols_with_default(30, 40, Vec3b::opencv_type(), (1., 2., 3.).into())?;
+	{
+		let mut roi = Mat::roi_mut(&mut mat, Rect::new(0, 0, 20, 30))?;
+		roi.par_pixels_mut::<Vec3b>()?.for_each(|px| px[1] = 20);
+	}
+	assert_eq!(Vec3b::from([1, 20, 3]), *mat.at_2d::<Vec3b>(29, 19)?);
+	assert_eq!(Vec3b::from([1, 2, 3]), *mat.at_2d::<Vec3b>(29, 20)?);
+	assert_eq!(30 * 40, mat.par_pixels_mut::<Vec3b>()?.count());
+
+	assert_matches!(
+		mat.par_pixels_mut::<u8>(),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
@@ -0,0 +1,65 @@
+#![cfg(target_os = "linux")]
+
+use std::os::unix::io::AsFd;
+
+use opencv::core::{Scalar, SharedMemoryMat, SharedMemoryMatReader, Size, Vec3b};
+use opencv::prelude::*;
+use opencv::Result;
+
+#[test]
+fn shared_memory_mat_ring_buffer() -> Result<()> {
+	let name = format!("opencv_rust_shared_memory_{}", std::process::id());
+	let mut writer = SharedMemoryMat::create(&name, &[24, 32], Vec3b::opencv_type(), 3)?;
+	assert!(SharedMemoryMat::create(&name, &[24, 32], Vec3b::opencv_type(), 3).is_err());
+	let reader = SharedMemoryMatReader::open(&name)?;
+	assert_eq!(&[24, 32], reader.sizes());
+	assert_eq!(&[32 * 3, 3], reader.steps());
+	assert_eq!(Vec3b::opencv_type(), reader.typ());
+	assert_eq!(3, reader.slots());
+	assert!(reader.latest()?.is_none());
+
+	for n in 1..=5 {
+		writer.next_slot()?.set_scalar(Scalar::all(f64::from(n * 10)))?;
+		assert_eq!(n as u64, writer.publish());
+	}
+	let (sequence, frame) = reader.latest()?.expect("Frame must be published");
+	assert_eq!(5, sequence);
+	assert_eq!(Size::new(32, 24), frame.size()?);
+	assert_eq!(Vec3b::all(50), *frame.at_2d::<Vec3b>(23, 31)?);
+	assert_eq!(Vec3b::all(40), *reader.frame(4)?.at_2d::<Vec3b>(0, 0)?);
+	// the slot of the frame 3 is the next one to be written
+	assert!(!reader.is_valid(3));
+	assert!(reader.frame(3).is_err());
+	assert!(reader.frame(6).is_err());
+
+	// the reader keeps the segment mapped after it's removed
+	drop(writer);
+	assert!(SharedMemoryMatReader::open(&name).is_err());
+	assert_eq!(Vec3b::all(50), *reader.frame(5)?.at_2d::<Vec3b>(0, 0)?);
+	Ok(())
+}
+
+#[test]
+fn shared_memory_mat_anonymous() -> Result<()> {
+	assert!(SharedMemoryMat::create_anonymous(&[2, 2], u8::opencv_type(), 1).is_err());
+	let mut writer = SharedMemoryMat::create_anonymous(&[4, 4, 4], f32::opencv_type(), 2)?;
+	assert_eq!(None, writer.name());
+	writer.next_slot()?.set_scalar(Scalar::all(1.5))?;
+	writer.publish();
+	let reader = SharedMemoryMatReader::from_fd(writer.as_fd().try_clone_to_owned().unwrap())?;
+	let frame = reader.frame(1)?;
+	assert_eq!(3, frame.dims());
+	assert_eq!(1.5, *frame.at_3d::<f32>(3, 3, 3)?);
+
+	// the copy is not affected by the writer
+	writer.next_slot()?.set_scalar(Scalar::all(2.5))?;
+	writer.publish();
+	writer.next_slot()?.set_scalar(Scalar::all(3.5))?;
+	writer.publish();
+	assert_eq!(1.5, *frame.at_3d::<f32>(0, 0, 0)?);
+	assert!(reader.frame(1).is_err());
+	// safe because the writer doesn't publish new frames while the mapped frame is alive
+	let frame = unsafe { reader.frame_ref(3) }?;
+	assert_eq!(3.5, *frame.at_3d::<f32>(0, 0, 0)?);
+	Ok(())
+}
@@ -0,0 +1,141 @@
+#![cfg(feature = "serde")]
+
+use opencv::core::{
+	DMatch, KeyPoint, Matx33d, Point, Point2f, Point3f, Rect, RotatedRect, Scalar, Size, Size2f, ToInputArray, Vec3b, Vec4i,
+};
+use opencv::prelude::*;
+use opencv::Result;
+
+#[test]
+fn serde_simple_types() -> Result<()> {
+	let pt = Point::new(10, -20);
+	assert_eq!(r#"{"x":10,"y":-20}"#, serde_json::to_string(&pt).unwrap());
+	assert_eq!(pt, serde_json::from_str(r#"{"x":10,"y":-20}"#).unwrap());
+
+	let pt3 = Point3f::new(1.5, 2., -3.);
+	assert_eq!(pt3, serde_json::from_str(&serde_json::to_string(&pt3).unwrap()).unwrap());
+
+	let size = Size::new(640, 480);
+	assert_eq!(r#"{"width":640,"height":480}"#, serde_json::to_string(&size).unwrap());
+	assert_eq!(size, serde_json::from_str(r#"{"width":640,"height":480}"#).unwrap());
+
+	let rect = Rect::new(1, 2, 3, 4);
+	assert_eq!(r#"{"x":1,"y":2,"width":3,"height":4}"#, serde_json::to_string(&rect).unwrap());
+	assert_eq!(rect, serde_json::from_str(r#"{"x":1,"y":2,"width":3,"height":4}"#).unwrap());
+
+	let vec = Vec4i::from([1, 2, 3, 4]);
+	assert_eq!("[1,2,3,4]", serde_json::to_string(&vec).unwrap());
+	assert_eq!(vec, serde_json::from_str("[1,2,3,4]").unwrap());
+	assert!(serde_json::from_str::<Vec4i>("[1,2,3]").is_err());
+
+	let scalar = Scalar::new(1., 2.5, 3., 4.);
+	assert_eq!("[1.0,2.5,3.0,4.0]", serde_json::to_string(&scalar).unwrap());
+	assert_eq!(scalar, serde_json::from_str("[1.0,2.5,3.0,4.0]").unwrap());
+
+	let matx = Matx33d::from_array([1., 2., 3., 4., 5., 6., 7., 8., 9.]);
+	let serialized = serde_json::to_string(&matx).unwrap();
+	assert_eq!("[1.0,2.0,3.0,4.0,5.0,6.0,7.0,8.0,9.0]", serialized);
+	assert_eq!(matx.val, serde_json::from_str::<Matx33d>(&serialized).unwrap().val);
+	Ok(())
+}
+
+#[test]
+fn serde_feature_types() -> Result<()> {
+	let dmatch = DMatch::new_index(1, 2, 3, 0.5)?;
+	let serialized = serde_json::to_string(&dmatch).unwrap();
+	assert_eq!(r#"{"query_idx":1,"train_idx":2,"img_idx":3,"distance":0.5}"#, serialized);
+	assert_eq!(dmatch, serde_json::from_str(&serialized).unwrap());
+
+	let rotated_rect = RotatedRect::new(Point2f::new(10., 20.), Size2f::new(30., 40.), 45.)?;
+	let serialized = serde_json::to_string(&rotated_rect).unwrap();
+	assert_eq!(rotated_rect, serde_json::from_str(&serialized).unwrap());
+
+	let key_point = KeyPoint::new_point(Point2f::new(1.5, 2.5), 3., 90., 0.75, 2, 7)?;
+	let serialized = serde_json::to_string(&key_point).unwrap();
+	assert_eq!(
+		r#"{"pt":{"x":1.5,"y":2.5},"size":3.0,"angle":90.0,"response":0.75,"octave":2,"class_id":7}"#,
+		serialized
+	);
+	let key_point: KeyPoint = serde_json::from_str(&serialized).unwrap();
+	assert_eq!(Point2f::new(1.5, 2.5), key_point.pt());
+	assert_eq!(3., key_point.size());
+	assert_eq!(90., key_point.angle());
+	assert_eq!(0.75, key_point.response());
+	assert_eq!(2, key_point.octave());
+	assert_eq!(7, key_point.class_id());
+	Ok(())
+}
+
+#[test]
+fn serde_mat() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[Vec3b::from([1, 2, 3]), Vec3b::from([4, 5, 6])]])?;
+	let serialized = serde_json::to_string(&mat).unwrap();
+	assert_eq!(
+		r#"{"type_id":"opencv-matrix","rows":1,"cols":2,"dt":"3u","data":[1,2,3,4,5,6]}"#,
+		serialized
+	);
+	let mat: Mat = serde_json::from_str(&serialized).unwrap();
+	assert_eq!(Vec3b::opencv_type(), mat.typ());
+	assert_eq!(Size::new(2, 1), mat.size()?);
+	assert_eq!(Vec3b::from([4, 5, 6]), *mat.at_2d::<Vec3b>(0, 1)?);
+
+	// non-continuous
+	let mat = Mat::new_rows_cols_with_default(4, 4, f32::opencv_type(), 1.5.into())?;
+	let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 3))?.input_array()?.get_mat_def()?;
+	assert!(!roi.is_continuous());
+	let deserialized: Mat = serde_json::from_str(&serde_json::to_string(&roi).unwrap()).unwrap();
+	assert_eq!(Size::new(2, 3), deserialized.size()?);
+	assert_eq!(&[1.5; 6], deserialized.data_typed::<f32>()?);
+
+	// n-dimensional
+	let mut mat = Mat::new_nd_with_default(&[2, 3, 4], f64::opencv_type(), 0.into())?;
+	*mat.at_3d_mut::<f64>(1, 2, 3)? = 10.;
+	let serialized = serde_json::to_string(&mat).unwrap();
+	assert!(serialized.starts_with(r#"{"type_id":"opencv-nd-matrix","sizes":[2,3,4],"dt":"d","data":[0.0,"#));
+	let mat: Mat = serde_json::from_str(&serialized).unwrap();
+	assert_eq!(3, mat.dims());
+	assert_eq!(10., *mat.at_3d::<f64>(1, 2, 3)?);
+
+	// empty
+	let mat: Mat = serde_json::from_str(&serde_json::to_string(&Mat::default()).unwrap()).unwrap();
+	assert!(mat.empty());
+
+	// invalid
+	assert!(serde_json::from_str::<Mat>(r#"{"type_id":"opencv-matrix","rows":1,"cols":2,"dt":"u","data":[1,2,3]}"#).is_err());
+	assert!(serde_json::from_str::<Mat>(r#"{"type_id":"opencv-matrix","rows":1,"cols":2,"dt":"u","data":[1]}"#).is_err());
+	assert!(serde_json::from_str::<Mat>(r#"{"type_id":"opencv-matrix","rows":1,"cols":2,"dt":"x","data":[1,2]}"#).is_err());
+	assert!(serde_json::from_str::<Mat>(r#"{"type_id":"opencv-matrix","rows":1,"cols":2,"dt":"u","data":[1,256]}"#).is_err());
+	assert!(serde_json::from_str::<Mat>(r#"{"type_id":"other","rows":1,"cols":2,"dt":"u","data":[1,2]}"#).is_err());
+	// huge sizes are rejected before allocating the Mat
+	assert!(
+		serde_json::from_str::<Mat>(r#"{"type_id":"opencv-matrix","rows":100000,"cols":100000,"dt":"4d","data":[1]}"#).is_err()
+	);
+	assert!(serde_json::from_str::<Mat>(r#"{"type_id":"opencv-matrix","rows":-1,"cols":2,"dt":"u","data":[1,2]}"#).is_err());
+	Ok(())
+}
+
+#[test]
+#[cfg(ocvrs_opencv_branch_4)]
+fn serde_mat_file_storage() -> Result<()> {
+	use opencv::core::{FileStorage, FileStorage_Mode};
+
+	let mat = Mat::from_slice_2d(&[[1i16, -2, 3], [4, 5, -6]])?;
+
+	let mut st = FileStorage::new_def(
+		".json",
+		i32::from(FileStorage_Mode::WRITE) | i32::from(FileStorage_Mode::MEMORY),
+	)?;
+	st.write_mat("mat", &mat)?;
+	let serialized = st.release_and_get_string()?;
+	let json: serde_json::Value = serde_json::from_str(&serialized).unwrap();
+	let deserialized: Mat = serde_json::from_value(json["mat"].clone()).unwrap();
+	assert_eq!(mat.size()?, deserialized.size()?);
+	assert_eq!(mat.data_typed::<i16>()?, deserialized.data_typed::<i16>()?);
+
+	let serialized = serde_json::to_string(&serde_json::json!({ "mat": mat })).unwrap();
+	let st = FileStorage::new_def(&serialized, i32::from(FileStorage_Mode::MEMORY))?;
+	let deserialized = st.get("mat")?.mat()?;
+	assert_eq!(mat.typ(), deserialized.typ());
+	assert_eq!(mat.data_typed::<i16>()?, deserialized.data_typed::<i16>()?);
+	Ok(())
+}
@@ -3,7 +3,7 @@ use std::ffi::c_char;
 
 use matches::assert_matches;
 
-use opencv::core::{DMatch, KeyPoint, Point2d, Point2f, Range, Scalar, SparseMat_Hdr, Vec4i, Vector};
+use opencv::core::{DMatch, KeyPoint, Point2d, Point2f, Range, Scalar, SparseMat_Hdr, ToInputArray, Vec4i, Vector};
 use opencv::prelude::*;
 use opencv::{core, Error, Result};
 
@@ -822,6 +822,171 @@ fn from_slice() -> Result<()> {
 	Ok(())
 }
 
+#[test]
+fn truncate_resize() -> Result<()> {
+	let mut vec = Vector::<i32>::from_slice(&[1, 2, 3, 4, 5]);
+	vec.truncate(10);
+	assert_eq!(5, vec.len());
+	vec.truncate(3);
+	assert_eq!(&[1, 2, 3], vec.as_slice());
+	vec.resize(5, 7);
+	assert_eq!(&[1, 2, 3, 7, 7], vec.as_slice());
+	vec.resize(1, 0);
+	assert_eq!(&[1], vec.as_slice());
+
+	let mut vec = Vector::<String>::new();
+	vec.resize(2, "abc");
+	assert_eq!(vec!["abc", "abc"], vec.to_vec());
+	vec.truncate(0);
+	assert!(vec.is_empty());
+	Ok(())
+}
+
+#[test]
+fn retain_dedup() -> Result<()> {
+	let mut vec = Vector::<i32>::from_slice(&[1, 2, 3, 4, 5, 6]);
+	vec.retain(|x| x % 2 == 0);
+	assert_eq!(&[2, 4, 6], vec.as_slice());
+
+	let mut vec = Vector::<String>::from_iter(["a", "bb", "c", "dd", "ee"]);
+	vec.retain(|x| x.len() == 2);
+	assert_eq!(vec!["bb", "dd", "ee"], vec.to_vec());
+
+	let mut vec = Vector::<i32>::from_slice(&[1, 1, 2, 3, 3, 3, 1, 4, 4]);
+	vec.dedup();
+	assert_eq!(&[1, 2, 3, 1, 4], vec.as_slice());
+	let mut vec = Vector::<bool>::from_iter([true, true, false, false, true]);
+	vec.dedup();
+	assert_eq!(vec![true, false, true], vec.to_vec());
+	let mut vec = Vector::<String>::from_iter(["a", "A", "b", "B", "b", "c"]);
+	vec.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+	assert_eq!(vec!["a", "b", "c"], vec.to_vec());
+	let mut vec = Vector::<i32>::new();
+	vec.dedup();
+	assert!(vec.is_empty());
+	Ok(())
+}
+
+#[test]
+fn drain_split_off_append() -> Result<()> {
+	let mut vec = Vector::<i32>::from_slice(&[1, 2, 3, 4, 5, 6]);
+	assert_eq!(vec![2, 3], vec.drain(1..3)?.collect::<Vec<_>>());
+	assert_eq!(&[1, 4, 5, 6], vec.as_slice());
+	assert_eq!(vec![5, 6], vec.drain(2..)?.collect::<Vec<_>>());
+	assert_eq!(&[1, 4], vec.as_slice());
+	assert_eq!(0, vec.drain(1..1)?.len());
+	assert_matches!(
+		vec.drain(1..3),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	let mut drain = vec.drain(..)?;
+	assert_eq!(Some(1), drain.next());
+	assert_eq!("[4]", format!("{drain:?}"));
+	assert_eq!(vec![4], drain.collect::<Vec<_>>());
+	assert!(vec.is_empty());
+
+	let mut vec = Vector::<Mat>::new();
+	for i in 0..4 {
+		vec.push(Mat::new_rows_cols_with_default(
+			1,
+			1,
+			i32::opencv_type(),
+			Scalar::all(f64::from(i)),
+		)?);
+	}
+	let mut tail = vec.split_off(1)?;
+	assert_eq!(1, vec.len());
+	assert_eq!(3, tail.len());
+	assert_eq!(1, *tail.get(0)?.at::<i32>(0)?);
+	assert!(vec.split_off(2).is_err());
+	vec.append(&mut tail);
+	assert!(tail.is_empty());
+	assert_eq!(4, vec.len());
+	assert_eq!(3, *vec.get(3)?.at::<i32>(0)?);
+
+	let mut vec = Vector::<Point2f>::from_slice(&[Point2f::new(1., 2.)]);
+	vec.extend_from_slice(&[Point2f::new(3., 4.), Point2f::new(5., 6.)]);
+	assert_eq!(
+		&[Point2f::new(1., 2.), Point2f::new(3., 4.), Point2f::new(5., 6.)],
+		vec.as_slice()
+	);
+	Ok(())
+}
+
+#[test]
+fn sort_by() -> Result<()> {
+	let mut vec = Vector::<i32>::from_slice(&[5, 1, 4, 2, 3]);
+	vec.sort_by(|a, b| b.cmp(a));
+	assert_eq!(&[5, 4, 3, 2, 1], vec.as_slice());
+
+	let mut vec = Vector::<String>::from_iter(["pear", "fig", "apple", "kiwi", "banana"]);
+	vec.sort_by(|a, b| a.len().cmp(&b.len()));
+	// the sort is stable
+	assert_eq!(vec!["fig", "pear", "kiwi", "apple", "banana"], vec.to_vec());
+
+	let mut vec = Vector::<Mat>::new();
+	for i in [3, 0, 2, 1] {
+		vec.push(Mat::new_rows_cols_with_default(
+			1,
+			1,
+			i32::opencv_type(),
+			Scalar::all(f64::from(i)),
+		)?);
+	}
+	let data_before = vec.get(0)?.data();
+	vec.sort_by(|a, b| a.at::<i32>(0).unwrap().cmp(b.at::<i32>(0).unwrap()));
+	assert_eq!(
+		vec![0, 1, 2, 3],
+		vec.iter().map(|m| *m.at::<i32>(0).unwrap()).collect::<Vec<_>>()
+	);
+	// the elements are moved, not copied
+	assert_eq!(data_before, vec.get(3)?.data());
+	Ok(())
+}
+
+#[test]
+fn first_last_index() -> Result<()> {
+	let mut vec = Vector::<i32>::new();
+	assert_eq!(None, vec.first());
+	assert_eq!(None, vec.last());
+	vec.extend_from_slice(&[10, 20, 30]);
+	assert_eq!(Some(10), vec.first());
+	assert_eq!(Some(30), vec.last());
+	assert_eq!(20, vec[1]);
+	assert_eq!(&[20, 30], &vec[1..]);
+	vec[2] = 35;
+	vec[..2].copy_from_slice(&[1, 2]);
+	assert_eq!(&[1, 2, 35], vec.as_slice());
+
+	let vec = Vector::<String>::from_iter(["a", "b"]);
+	assert_eq!(Some("a".to_string()), vec.first());
+	assert_eq!(Some("b".to_string()), vec.last());
+	Ok(())
+}
+
+#[test]
+fn slice_view() -> Result<()> {
+	let points = vec![Point2f::new(1., 2.), Point2f::new(3., 4.), Point2f::new(5., 6.)];
+	let view = Vector::with_slice_view(&points);
+	assert_eq!(3, view.len());
+	assert_eq!(points.as_slice(), view.as_slice());
+	let input = view.input_array()?;
+	assert_eq!(Point2f::opencv_type(), input.typ_def()?);
+	assert_eq!(3, input.total_def()?);
+	let mat = input.get_mat_def()?;
+	assert_eq!(points.as_ptr().cast::<u8>(), mat.data());
+	assert_eq!(3, mat.check_vector_def(2)?);
+	assert_eq!(Scalar::new(9., 12., 0., 0.), core::sum_elems(&view)?);
+	assert_eq!(core::sum_elems(&Vector::from_slice(&points))?, core::sum_elems(&view)?);
+
+	let empty = Vector::<i32>::with_slice_view(&[]);
+	assert_eq!(0, empty.input_array()?.total_def()?);
+	Ok(())
+}
+
 #[test]
 fn must_be_clone() {
 	fn must_be_clone(_: impl Clone) {}
@@ -0,0 +1,105 @@
+#![cfg(all(feature = "async", ocvrs_has_module_videoio, ocvrs_opencv_branch_4))]
+
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use futures::executor::block_on_stream;
+
+use opencv::core::{Scalar, Size, Vec3b};
+use opencv::prelude::*;
+use opencv::videoio::{DropPolicy, VideoCapture, VideoCaptureStream, VideoWriter, CAP_OPENCV_MJPEG};
+use opencv::Result;
+
+const FRAMES: usize = 5;
+
+/// Writes the MJPEG video file with `FRAMES` uniformly filled frames, the value of the n-th frame is `n * 50`
+fn write_video(name: &str) -> Result<PathBuf> {
+	let path = std::env::temp_dir().join(format!("opencv_rust_{name}_{}.avi", std::process::id()));
+	let mut writer = VideoWriter::new_with_backend(
+		path.to_str().unwrap(),
+		CAP_OPENCV_MJPEG,
+		VideoWriter::fourcc('M', 'J', 'P', 'G')?,
+		10.,
+		Size::new(32, 24),
+		false,
+	)?;
+	assert!(writer.is_opened()?);
+	for n in 0..FRAMES {
+		let frame = Mat::new_rows_cols_with_default(24, 32, u8::opencv_type(), Scalar::all((n * 50) as f64))?;
+		writer.write(&frame)?;
+	}
+	writer.release()?;
+	Ok(path)
+}
+
+/// The frames are decoded as BGR
+fn frame_index(frame: &Mat) -> Result<usize> {
+	Ok((f64::from(frame.at_2d::<Vec3b>(12, 16)?[0]) / 50.).round() as usize)
+}
+
+fn finished_stream(name: &str, buffer_size: usize, policy: DropPolicy) -> Result<VideoCaptureStream> {
+	let path = write_video(name)?;
+	let capture = VideoCapture::from_file(path.to_str().unwrap(), CAP_OPENCV_MJPEG)?;
+	assert!(capture.is_opened()?);
+	let stream = VideoCaptureStream::new(capture, buffer_size, policy)?;
+	while !stream.is_finished() {
+		thread::sleep(Duration::from_millis(10));
+	}
+	let _ = std::fs::remove_file(path);
+	Ok(stream)
+}
+
+#[test]
+fn video_capture_stream_end() -> Result<()> {
+	let stream = finished_stream("stream_end", FRAMES, DropPolicy::DropOldest)?;
+	assert_eq!(0, stream.dropped_frames());
+	let frames = block_on_stream(stream).collect::<Result<Vec<_>>>()?;
+	assert_eq!(FRAMES, frames.len());
+	for (n, frame) in frames.iter().enumerate() {
+		assert_eq!(Size::new(32, 24), frame.size()?);
+		assert_eq!(n, frame_index(frame)?);
+	}
+	Ok(())
+}
+
+#[test]
+fn video_capture_stream_drop_policy() -> Result<()> {
+	let stream = finished_stream("drop_oldest", 2, DropPolicy::DropOldest)?;
+	assert_eq!(FRAMES - 2, stream.dropped_frames());
+	let indices = block_on_stream(stream)
+		.map(|frame| frame.and_then(|frame| frame_index(&frame)))
+		.collect::<Result<Vec<_>>>()?;
+	assert_eq!(vec![FRAMES - 2, FRAMES - 1], indices);
+
+	let stream = finished_stream("drop_newest", 2, DropPolicy::DropNewest)?;
+	assert_eq!(FRAMES - 2, stream.dropped_frames());
+	let indices = block_on_stream(stream)
+		.map(|frame| frame.and_then(|frame| frame_index(&frame)))
+		.collect::<Result<Vec<_>>>()?;
+	assert_eq!(vec![0, 1], indices);
+	Ok(())
+}
+
+#[test]
+fn video_capture_stream_stop() -> Result<()> {
+	let path = write_video("stream_stop")?;
+	let capture = VideoCapture::from_file(path.to_str().unwrap(), CAP_OPENCV_MJPEG)?;
+	let stream = VideoCaptureStream::new(capture, 1, DropPolicy::DropNewest)?;
+	stream.stop();
+	// at most the frame being read at the time of stop() is yielded
+	assert!(block_on_stream(stream).count() <= 1);
+	let _ = std::fs::remove_file(path);
+
+	let path = write_video("stream_close")?;
+	let capture = VideoCapture::from_file(path.to_str().unwrap(), CAP_OPENCV_MJPEG)?;
+	let mut stream = VideoCaptureStream::new(capture, FRAMES, DropPolicy::DropNewest)?;
+	stream.close()?;
+	assert!(stream.is_finished());
+	// the buffered frames are still available after the capture thread is joined
+	assert!(block_on_stream(stream).count() <= FRAMES);
+	let _ = std::fs::remove_file(path);
+
+	assert!(VideoCaptureStream::new(VideoCapture::default()?, 0, DropPolicy::DropOldest).is_err());
+	Ok(())
+}